@@ -0,0 +1,236 @@
+use alloc::vec::Vec;
+use crate::frame::Frame;
+use crate::geometry::MAX_ROWS;
+use crate::lcd1602::MarqueStyle;
+
+// Software marquee. cds() can only shift every row at once, so instead each row gets its own track that
+// scrolls through a window of the frame; pinned rows simply sit there. Nothing here sleeps: call tick() from
+// the main loop as often as you like (between key reads) and flush the frame whenever it says something moved.
+
+const BLANK: u8 = 0b0010_0000;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Motion {
+    Pinned,
+    Left,
+    Right
+}
+
+struct Track {
+    text: Vec<u8>, // ← CGROM codes
+    motion: Motion,
+    offset: u16,   // ← index into the loop (text + gap) of the leftmost visible cell
+    due: u32       // ← now_ms at which this track next moves
+}
+
+pub struct Marquee {
+    style: MarqueStyle,
+    tracks: [Track; MAX_ROWS],
+    rows: u8,
+    step: u16,     // ← ms per one-cell shift
+    pause: u16,    // ← ms to hold whenever the text is back home (0 → never stop)
+    gap: u8,       // ← blank cells between the end of the text and its next lap
+    started: bool
+}
+
+impl Marquee {
+    pub fn new(style: MarqueStyle, rows: u8) -> Self {
+        let mut marquee = Self {
+            style,
+            tracks: core::array::from_fn(|_| Track { text: Vec::new(), motion: Motion::Pinned, offset: 0, due: 0 }),
+            rows: rows.min(MAX_ROWS as u8),
+            step: 200,
+            pause: 1000,
+            gap: 4,
+            started: false
+        };
+
+        marquee.set_style(style);
+        marquee
+    }
+
+    pub fn set_row(&mut self, row: u8, codes: &[u8]) {
+        if let Some(track) = self.tracks.get_mut(row as usize) {
+            track.text.clear();
+            track.text.extend_from_slice(codes);
+            track.offset = 0;
+        }
+        self.started = false;
+    }
+
+    pub fn set_style(&mut self, style: MarqueStyle) { // ← every row goes back home
+        self.style = style;
+        let last = self.rows.saturating_sub(1); // ← rows == 0 drives nothing, but mustn't underflow getting there
+
+        for (row, track) in self.tracks.iter_mut().enumerate().take(self.rows as usize) {
+            let row = row as u8;
+            track.motion = match style {
+                MarqueStyle::SoloHighL if row == 0 => Motion::Left,
+                MarqueStyle::SoloHighR if row == 0 => Motion::Right,
+                MarqueStyle::SoloLowL if row == last => Motion::Left,
+                MarqueStyle::SoloLowR if row == last => Motion::Right,
+                MarqueStyle::SyncLeft => Motion::Left,
+                MarqueStyle::SyncRight => Motion::Right,
+                MarqueStyle::GearLeft => if row & 1 == 0 { Motion::Left } else { Motion::Right }, // ← meshed gears turn opposite ways
+                MarqueStyle::GearRight => if row & 1 == 0 { Motion::Right } else { Motion::Left },
+                _ => Motion::Pinned
+            };
+            track.offset = 0;
+        }
+
+        self.started = false;
+    }
+
+    pub fn set_timing(&mut self, step: u16, pause: u16) {
+        self.step = step.max(1);
+        self.pause = pause;
+    }
+
+    pub fn set_gap(&mut self, gap: u8) {
+        self.gap = gap;
+    }
+
+    pub fn style(&self) -> MarqueStyle {
+        self.style
+    }
+
+    pub fn tick(&mut self, now_ms: u32, frame: &mut Frame) -> bool { // ← true → the frame changed, flush it
+        let cols = frame.geometry().cols;
+
+        if !self.started {
+            for row in 0..self.rows {
+                let track = &mut self.tracks[row as usize];
+                track.due = now_ms.wrapping_add(self.pause as u32); // ← graceful hold before the first shift too
+                Self::draw(track, row, cols, self.gap, frame);
+            }
+
+            self.started = true;
+            return true;
+        }
+
+        let mut moved = false;
+        for row in 0..self.rows {
+            let track = &mut self.tracks[row as usize];
+            if track.motion == Motion::Pinned || (now_ms.wrapping_sub(track.due) as i32) < 0 {
+                continue;
+            }
+
+            let lap = Self::lap(track, cols, self.gap);
+            track.offset = match track.motion {
+                Motion::Left => (track.offset + 1) % lap,
+                _ => (track.offset + lap - 1) % lap
+            };
+
+            let hold = if track.offset == 0 && self.pause > 0 { self.pause } else { self.step };
+            track.due = now_ms.wrapping_add(hold as u32);
+
+            Self::draw(track, row, cols, self.gap, frame);
+            moved = true;
+        }
+
+        moved
+    }
+
+    fn lap(track: &Track, cols: u8, gap: u8) -> u16 { // ← cells before the text comes round again; short text still crosses the whole row
+        (track.text.len() as u16 + gap as u16).max(cols as u16)
+    }
+
+    fn draw(track: &Track, row: u8, cols: u8, gap: u8, frame: &mut Frame) {
+        let lap = Self::lap(track, cols, gap);
+
+        frame.at(row, 0);
+        for col in 0..cols as u16 {
+            let i = ((track.offset + col) % lap) as usize;
+            frame.put(*track.text.get(i).unwrap_or(&BLANK));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use super::Marquee;
+    use crate::frame::Frame;
+    use crate::geometry::Geometry;
+    use crate::lcd1602::MarqueStyle;
+
+    fn row(frame: &Frame, row: u8) -> String {
+        (0..frame.geometry().cols).map(|col| frame.cell(row, col) as char).collect()
+    }
+
+    fn marquee(style: MarqueStyle, pause: u16) -> (Marquee, Frame) { // ← 16 cols, so a lap is 16 shifts: "TOP" + 13 blanks
+        let mut marquee = Marquee::new(style, 2);
+        marquee.set_row(0, b"TOP");
+        marquee.set_row(1, b"LOW");
+        marquee.set_timing(100, pause);
+
+        let mut frame = Frame::new(Geometry::LCD1602);
+        assert!(marquee.tick(0, &mut frame));
+        (marquee, frame)
+    }
+
+    #[test]
+    fn styles() { // ← one shift each: left wraps the head round to the far end, right pulls in the gap
+        let moved = |text: &str, motion: char| match motion {
+            'L' => format!("{}{:13}{}", &text[1..], "", &text[..1]),
+            'R' => format!(" {}{:12}", text, ""),
+            _ => format!("{:16}", text)
+        };
+
+        for (style, motions) in [
+            (MarqueStyle::SoloHighL, "LP"),
+            (MarqueStyle::SoloHighR, "RP"),
+            (MarqueStyle::SoloLowL, "PL"),
+            (MarqueStyle::SoloLowR, "PR"),
+            (MarqueStyle::SyncLeft, "LL"),
+            (MarqueStyle::SyncRight, "RR"),
+            (MarqueStyle::GearLeft, "LR"),
+            (MarqueStyle::GearRight, "RL")
+        ] {
+            let (mut marquee, mut frame) = marquee(style, 500);
+            assert!(!marquee.tick(499, &mut frame)); // ← graceful hold before the first shift
+            assert!(marquee.tick(500, &mut frame));
+
+            let motions: Vec<char> = motions.chars().collect();
+            assert_eq!(row(&frame, 0), moved("TOP", motions[0]));
+            assert_eq!(row(&frame, 1), moved("LOW", motions[1]));
+        }
+    }
+
+    #[test]
+    fn pauses_at_home() {
+        let (mut marquee, mut frame) = marquee(MarqueStyle::SoloHighL, 500);
+        for now in (500..=2000).step_by(100) { // ← 16 shifts, the last one lands back home
+            assert!(marquee.tick(now, &mut frame));
+        }
+        assert_eq!(row(&frame, 0), "TOP             ");
+
+        assert!(!marquee.tick(2100, &mut frame));
+        assert!(!marquee.tick(2499, &mut frame));
+        assert!(marquee.tick(2500, &mut frame));
+        assert_eq!(row(&frame, 0), "OP             T");
+    }
+
+    #[test]
+    fn no_pause() { // ← pause 0 → straight through home without stopping
+        let (mut marquee, mut frame) = marquee(MarqueStyle::SoloHighR, 0);
+        for now in 0..16 {
+            assert!(marquee.tick(now * 100, &mut frame));
+        }
+        assert_eq!(row(&frame, 0), "TOP             ");
+        assert!(marquee.tick(1600, &mut frame));
+        assert_eq!(row(&frame, 0), " TOP            ");
+    }
+
+    #[test]
+    fn no_rows() {
+        let mut marquee = Marquee::new(MarqueStyle::SoloLowL, 0);
+        marquee.set_style(MarqueStyle::GearRight);
+
+        let mut frame = Frame::new(Geometry::LCD1602);
+        marquee.tick(0, &mut frame);
+        assert_eq!(row(&frame, 1), "                ");
+    }
+}
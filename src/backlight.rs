@@ -0,0 +1,80 @@
+use core::convert::Infallible;
+use embedded_hal::pwm::{ErrorType, SetDutyCycle};
+
+// Backlight bookkeeping shared by both backends. The driver has no clock of its own, so auto-dim works like the
+// marquee: the main loop hands in now_ms, calls wake() on anything a human did (key press, new delivery) and
+// idle() whenever it has a spare moment. The backend only ever sees the level it should drive right now.
+
+pub const FULL: u8 = 255;
+
+pub struct NoBacklight; // ← parallel panel with the LED pin tied straight to 5 V; every level is "on"
+
+impl ErrorType for NoBacklight {
+    type Error = Infallible;
+}
+
+impl SetDutyCycle for NoBacklight {
+    fn max_duty_cycle(&self) -> u16 {
+        FULL as u16
+    }
+
+    fn set_duty_cycle(&mut self, _: u16) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+pub struct AutoDim {
+    bright: u8, // ← the level set_backlight asked for; what wake() goes back to
+    dim: u8,    // ← the level after `after` ms of nothing happening (0 → lights out)
+    after: u32, // ← 0 → never dim
+    last: u32,  // ← now_ms of the last wake()
+    dimmed: bool
+}
+
+impl Default for AutoDim {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AutoDim {
+    pub fn new() -> Self {
+        Self { bright: FULL, dim: 0, after: 0, last: 0, dimmed: false }
+    }
+
+    pub fn set(&mut self, after_ms: u32, dim: u8) {
+        self.after = after_ms;
+        self.dim = dim;
+    }
+
+    pub fn bright(&self) -> u8 {
+        self.bright
+    }
+
+    pub fn set_bright(&mut self, level: u8) { // ← doesn't count as activity; a dimmed panel stays dimmed until wake()
+        self.bright = level;
+    }
+
+    pub fn level(&self) -> u8 { // ← what the panel should be showing right now
+        if self.dimmed { self.dim.min(self.bright) } else { self.bright }
+    }
+
+    pub fn wake(&mut self, now_ms: u32) -> Option<u8> { // ← Some → the level changed, drive it
+        self.last = now_ms;
+        if !self.dimmed {
+            return None;
+        }
+
+        self.dimmed = false;
+        Some(self.level())
+    }
+
+    pub fn idle(&mut self, now_ms: u32) -> Option<u8> {
+        if self.dimmed || self.after == 0 || now_ms.wrapping_sub(self.last) < self.after {
+            return None;
+        }
+
+        self.dimmed = true;
+        Some(self.level())
+    }
+}
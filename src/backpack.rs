@@ -0,0 +1,130 @@
+use embedded_hal::i2c::{Error, ErrorKind, I2c};
+use crate::lcd1602::LcdError;
+
+// Which expander pin drives which LCD line. Every I2C backpack is the same idea (an 8-bit port expander in front
+// of a 4-bit HD44780 bus) but suppliers wire the port however the PCB routed best, so the layout is data, not
+// constants. Pick a preset, or build one from the silkscreen/continuity tester and pass it to set_pin_map.
+
+pub const MCP_IODIR: u8 = 0x00; // ← MCP23008 registers; the PCF8574 has none, every byte is the port
+pub const MCP_IPOL: u8 = 0x01;
+pub const MCP_GPIO: u8 = 0x09;
+const PCF_IDLE: u8 = 0xFF; // ← every line weakly high, as the PCF8574 powers up; E high with RW high is just a read
+
+// Where expanders can sit: PCF8574 (and MCP23008) at 0x20-0x27, PCF8574A at 0x38-0x3F, A0-A2 jumpers picking
+// the low three bits. 0x27 and 0x3F (no jumpers bridged) are what ships.
+pub const ADDRESSES: [core::ops::RangeInclusive<u8>; 2] = [0x20..=0x27, 0x38..=0x3F];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Expander {
+    Pcf8574, // ← quasi-bidirectional port; a write is the port, a read is the pins
+    Mcp23008 // ← register-addressed; GPIO writes go through MCP_GPIO, and IODIR has to be set to outputs first
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PinMap {
+    pub expander: Expander,
+    pub rs: u8,
+    pub rw: Option<u8>, // ← None → tied to GND; nothing can be read back (no BF polling, no dtr)
+    pub e: u8,
+    pub bl: Option<u8>,
+    pub bl_low: bool,   // ← backlight transistor switches on with the pin low
+    pub d: [u8; 4]      // ← D4-D7
+}
+
+impl PinMap {
+    // YwRobot LCM1602 and the countless clones of it: P0=RS P1=RW P2=E P3=BL P4-P7=D4-D7. Default.
+    pub const YWROBOT: PinMap = PinMap { expander: Expander::Pcf8574, rs: 0, rw: Some(1), e: 2, bl: Some(3), bl_low: false, d: [4, 5, 6, 7] };
+
+    // Older "LCM1602 IIC A0 A1 A2" (and mjkdz) boards: data on the low nibble, control on top, backlight active-low.
+    pub const LCM1602: PinMap = PinMap { expander: Expander::Pcf8574, rs: 6, rw: Some(5), e: 4, bl: Some(7), bl_low: true, d: [0, 1, 2, 3] };
+
+    // Adafruit I2C/SPI character LCD backpack: an MCP23008 with RW strapped to ground. GP0 is unused.
+    pub const ADAFRUIT: PinMap = PinMap { expander: Expander::Mcp23008, rs: 1, rw: None, e: 2, bl: Some(7), bl_low: false, d: [3, 4, 5, 6] };
+
+    pub fn stock(expander: Expander) -> PinMap { // ← the board each expander usually comes on
+        match expander {
+            Expander::Pcf8574 => PinMap::YWROBOT,
+            Expander::Mcp23008 => PinMap::ADAFRUIT
+        }
+    }
+
+    pub fn readable(&self) -> bool { // ← can the panel be read at all (BF, AC, DDRAM)?
+        self.rw.is_some() && self.expander == Expander::Pcf8574
+    }
+
+    pub fn bl_state(&self, on: bool) -> bool { // ← pin level for a lit (or dark) backlight
+        on != self.bl_low
+    }
+}
+
+pub fn probe<I: I2c>(i2c: &mut I) -> Result<(u8, Expander), LcdError> { // ← first expander that answers, and which kind; a 1-byte read only samples the port
+    let mut buf = [0u8; 1];
+
+    for target in ADDRESSES.into_iter().flatten() {
+        match i2c.read(target, &mut buf) {
+            Ok(()) => return Ok((target, identify(i2c, target)?)),
+            Err(e) if matches!(e.kind(), ErrorKind::NoAcknowledge(_)) => continue, // ← nobody home, next
+            Err(e) => return Err(LcdError::from_i2c(e)) // ← bus trouble; the rest of the scan would only lie
+        }
+    }
+
+    Err(LcdError::NotFound)
+}
+
+fn identify<I: I2c>(i2c: &mut I, target: u8) -> Result<Expander, LcdError> { // ← both kinds sit at 0x20-0x27
+    if !ADDRESSES[0].contains(&target) {
+        return Ok(Expander::Pcf8574); // ← only the PCF8574A lives up at 0x38
+    }
+
+    // An MCP23008 takes 0x01 as a register pointer and answers with IPOL, 0x00 unless someone set it (we never do).
+    // A PCF8574 takes it as the port: P0 goes weakly high with nothing pulling it down (RW is low), and reads back 1.
+    // That port also has P3 low, which is a YwRobot's backlight, so it goes straight back to 0xFF (power-on, lit).
+    let mut buf = [0u8; 1];
+    i2c.write_read(target, &[MCP_IPOL], &mut buf).map_err(LcdError::from_i2c)?;
+    if buf[0] & 0x01 == 0 {
+        return Ok(Expander::Mcp23008);
+    }
+
+    i2c.write(target, &[PCF_IDLE]).map_err(LcdError::from_i2c)?;
+    Ok(Expander::Pcf8574)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use crate::lcd1602::LcdError;
+    use crate::mock::Bus;
+    use super::{probe, Expander};
+
+    fn bus(answers: &[u8], mcp: &[u8]) -> Bus {
+        let bus = Bus::default();
+        bus.0.borrow_mut().answers = answers.to_vec();
+        bus.0.borrow_mut().mcp = mcp.to_vec();
+        bus
+    }
+
+    #[test]
+    fn pcf8574() {
+        assert_eq!(probe(&mut bus(&[0x27], &[])), Ok((0x27, Expander::Pcf8574)));
+        assert_eq!(probe(&mut bus(&[0x3F], &[])), Ok((0x3F, Expander::Pcf8574))); // ← the A variant, no question asked
+    }
+
+    #[test]
+    fn pcf8574_backlight() { // ← P3 dips for the question, and comes straight back
+        let mut bus = bus(&[0x27], &[]);
+        probe(&mut bus).unwrap();
+        assert_eq!(bus.0.borrow().writes, vec![(0x27, vec![0x01]), (0x27, vec![0xFF])]);
+    }
+
+    #[test]
+    fn mcp23008() {
+        let mut bus = bus(&[0x20], &[0x20]);
+        assert_eq!(probe(&mut bus), Ok((0x20, Expander::Mcp23008)));
+        assert_eq!(bus.0.borrow().writes, vec![(0x20, vec![0x01])]); // ← only ever touched IPOL's pointer
+    }
+
+    #[test]
+    fn nobody() {
+        assert_eq!(probe(&mut bus(&[0x50], &[])), Err(LcdError::NotFound));
+    }
+}
@@ -0,0 +1,89 @@
+use alloc::string::String;
+use crate::cgram::GlyphBank;
+
+// Two-row "big" digits for reading across a corridor. Each digit is 3 cells wide and built from a handful of
+// bar glyphs plus the full block the widgets already register, so a whole "12:34" or "120m" needs at most five
+// CGRAM slots. The segment glyphs sit in the private use area so they can never collide with a real char.
+
+pub const BIG_UB: char = '\u{E000}';  // ← upper bar
+pub const BIG_LB: char = '\u{E001}';  // ← lower bar
+pub const BIG_UMB: char = '\u{E002}'; // ← upper + lower bar (the middle stroke of 2, 3, 5, 6, 8, 9)
+pub const BIG_DOT: char = '\u{E003}';
+pub const BIG_STEM: char = '\u{E004}'; // ← thin centre stroke, m's middle leg
+
+const FULL: char = '█';
+
+const GLYPHS: [(char, [u8; 8]); 5] = [
+    (BIG_UB, [0x1F, 0x1F, 0x1F, 0x00, 0x00, 0x00, 0x00, 0x00]),
+    (BIG_LB, [0x00, 0x00, 0x00, 0x00, 0x00, 0x1F, 0x1F, 0x1F]),
+    (BIG_UMB, [0x1F, 0x1F, 0x1F, 0x00, 0x00, 0x1F, 0x1F, 0x1F]),
+    (BIG_DOT, [0x00, 0x00, 0x0E, 0x0E, 0x0E, 0x00, 0x00, 0x00]),
+    (BIG_STEM, [0x0E, 0x0E, 0x0E, 0x0E, 0x0E, 0x0E, 0x0E, 0x0E])
+];
+
+pub fn register(bank: &mut GlyphBank) {
+    for (c, rows) in GLYPHS {
+        bank.register(c, rows);
+    }
+}
+
+fn cells(c: char) -> Option<(&'static [char], &'static [char])> { // ← (top, bottom)
+    const B: char = ' ';
+    const F: char = FULL;
+    const U: char = BIG_UB;
+    const L: char = BIG_LB;
+    const M: char = BIG_UMB;
+
+    Some(match c {
+        '0' => (&[F, U, F], &[F, L, F]),
+        '1' => (&[U, F, B], &[L, F, L]),
+        '2' => (&[M, M, F], &[F, L, L]),
+        '3' => (&[M, M, F], &[L, L, F]),
+        '4' => (&[F, L, F], &[B, B, F]),
+        '5' => (&[F, M, M], &[L, L, F]),
+        '6' => (&[F, M, M], &[F, L, F]),
+        '7' => (&[U, U, F], &[B, B, F]),
+        '8' => (&[F, M, F], &[F, L, F]),
+        '9' => (&[F, M, F], &[L, L, F]),
+        'm' => (&[L, L, L], &[F, BIG_STEM, F]),
+        ':' => (&[BIG_DOT], &[BIG_DOT]),
+        ' ' => (&[B], &[B]), // ← as wide as ':', so a blinking colon doesn't shove the digits around
+        _ => return None
+    })
+}
+
+fn spaced(c: char) -> bool { // ← 1-wide glyphs bring their own breathing room
+    !matches!(c, ':' | ' ')
+}
+
+pub struct BigText {
+    pub top: String,
+    pub bottom: String,
+    pub width: u8,
+    pub unmapped: Option<char> // ← first char with no big form; it's left out
+}
+
+pub fn compose(str: &str) -> BigText {
+    let mut big = BigText { top: String::new(), bottom: String::new(), width: 0, unmapped: None };
+    let mut prev: Option<char> = None;
+
+    for c in str.chars() {
+        let Some((top, bottom)) = cells(c) else {
+            big.unmapped.get_or_insert(c);
+            continue;
+        };
+
+        if prev.is_some_and(spaced) && spaced(c) {
+            big.top.push(' ');
+            big.bottom.push(' ');
+            big.width += 1;
+        }
+
+        big.top.extend(top);
+        big.bottom.extend(bottom);
+        big.width += top.len() as u8;
+        prev = Some(c);
+    }
+
+    big
+}
@@ -0,0 +1,169 @@
+use alloc::vec::Vec;
+
+// The HD44780 only has 8 CGRAM slots (5x8 mode), but nothing stops us from knowing about more glyphs than that.
+// GlyphBank keeps the full registry and decides which 8 are resident; it never touches the bus itself, instead
+// handing back (slot, rows) pairs for the caller to upload (see HD44780Util::cgput). Keeps the borrow checker happy.
+
+pub const CGRAM_SLOTS: usize = 8;
+const DDRAM_CELLS: usize = 80;     // ← 2 × 40, whatever the panel shows of it
+const BLANK: u8 = 0b0010_0000;     // ← ' '
+
+#[derive(Debug)]
+pub enum GlyphError {
+    Unregistered(char),
+    Overflow(u8) // ← distinct custom glyphs one screen would need (always > 8)
+}
+
+pub struct GlyphBank {
+    glyphs: Vec<(char, [u8; 8])>,         // ← registry; rows are 5 LSB per line, top → bottom
+    slots: [Option<char>; CGRAM_SLOTS],  // ← what each slot currently holds
+    stamps: [u32; CGRAM_SLOTS],          // ← LRU clock at last use
+    clock: u32,
+    onscreen: u8,                        // ← bitmask of slots drawn since the last clear (or what the last flush left); never evicted
+    cells: [u8; DDRAM_CELLS]             // ← DDRAM as drawn through Lcd1602::disp_sym, so a slot unpins once nothing shows it
+}
+
+impl Default for GlyphBank {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GlyphBank {
+    pub fn new() -> Self {
+        Self { glyphs: Vec::new(), slots: [None; CGRAM_SLOTS], stamps: [0; CGRAM_SLOTS], clock: 0, onscreen: 0, cells: [BLANK; DDRAM_CELLS] }
+    }
+
+    pub fn register(&mut self, sym: char, rows: [u8; 8]) { // ← re-registering replaces the bitmap (and drops the stale upload)
+        match self.glyphs.iter_mut().find(|(c, _)| *c == sym) {
+            Some(glyph) => {
+                glyph.1 = rows;
+
+                if let Some(slot) = self.slot_of(sym) {
+                    self.slots[slot] = None;
+                    self.onscreen &= !(1 << slot);
+                }
+            }
+            None => self.glyphs.push((sym, rows))
+        }
+    }
+
+    pub fn knows(&self, sym: char) -> bool {
+        self.rows_of(sym).is_some()
+    }
+
+    pub fn seat(&mut self, slot: u8, sym: char) { // ← declare a slot as already uploaded (e.g. by cgload on init)
+        self.slots[slot as usize & 0x7] = Some(sym);
+        self.stamps[slot as usize & 0x7] = self.tick();
+    }
+
+    pub fn resolve(&mut self, sym: char) -> Result<(u8, Option<[u8; 8]>), GlyphError> { // ← (CGROM code, rows to upload first if not resident)
+        let rows = self.rows_of(sym).ok_or(GlyphError::Unregistered(sym))?;
+
+        let (slot, load) = match self.slot_of(sym) {
+            Some(slot) => (slot, None),
+            None => {
+                let slot = self.victim().ok_or(GlyphError::Overflow(CGRAM_SLOTS as u8 + 1))?;
+                self.slots[slot] = Some(sym);
+                (slot, Some(rows))
+            }
+        };
+
+        self.stamps[slot] = self.tick();
+        self.onscreen |= 1 << slot;
+        Ok((slot as u8, load))
+    }
+
+    pub fn plan(&mut self, str: &str) -> Result<Vec<(u8, [u8; 8])>, GlyphError> { // ← make every custom glyph in str resident at once
+        let mut needed: Vec<char> = Vec::new();
+
+        for c in str.chars() {
+            if self.knows(c) && !needed.contains(&c) {
+                needed.push(c);
+            }
+        }
+
+        // Whatever is still on screen from earlier draws counts against the 8 slots as well.
+        let held = (0..CGRAM_SLOTS).filter(|&i| self.onscreen & (1 << i) != 0 && !self.slots[i].is_some_and(|c| needed.contains(&c))).count();
+
+        if needed.len() + held > CGRAM_SLOTS {
+            return Err(GlyphError::Overflow((needed.len() + held) as u8));
+        }
+
+        // Pin the ones already resident first so loading the rest can't evict them.
+        for c in needed.iter() {
+            if let Some(slot) = self.slot_of(*c) {
+                self.onscreen |= 1 << slot;
+            }
+        }
+
+        let mut loads = Vec::new();
+        for c in needed {
+            if let (slot, Some(rows)) = self.resolve(c)? {
+                loads.push((slot, rows));
+            }
+        }
+
+        Ok(loads)
+    }
+
+    pub fn resident(&self, slot: u8) -> Option<char> { // ← which char a CGRAM code currently draws (0x08-0x0F mirror 0x00-0x07)
+        self.slots[slot as usize & 0x7]
+    }
+
+    pub fn forget(&mut self, slot: u8) { // ← someone else rewrote the slot behind our back; upload again before trusting it
+        self.slots[slot as usize & 0x7] = None;
+        self.onscreen &= !(1 << (slot & 0x7));
+    }
+
+    pub fn release(&mut self) { // ← screen cleared; every slot is fair game again
+        self.onscreen = 0;
+        self.cells = [BLANK; DDRAM_CELLS];
+    }
+
+    pub fn wrote(&mut self, addr: u8, code: u8) { // ← code just landed at DDRAM addr; whatever it covered may be gone for good
+        let i = match addr {
+            0x00..=0x27 => addr as usize,
+            0x40..=0x67 => addr as usize - 0x40 + DDRAM_CELLS / 2,
+            _ => return
+        };
+
+        let old = core::mem::replace(&mut self.cells[i], code);
+        if let Some(slot) = cgram_slot(old) {
+            if !self.cells.iter().any(|&c| cgram_slot(c) == Some(slot)) {
+                self.onscreen &= !(1 << slot);
+            }
+        }
+    }
+
+    pub fn pin(&mut self, slots: u8) { // ← bitmask of slots that are on screen after all (see HD44780Util::flush)
+        self.onscreen |= slots;
+    }
+
+    fn victim(&self) -> Option<usize> { // ← empty slot if any, else least-recently-used one that isn't on screen
+        if let Some(empty) = self.slots.iter().position(|s| s.is_none()) {
+            return Some(empty);
+        }
+
+        (0..CGRAM_SLOTS)
+            .filter(|&i| self.onscreen & (1 << i) == 0)
+            .min_by_key(|&i| self.stamps[i])
+    }
+
+    fn slot_of(&self, sym: char) -> Option<usize> {
+        self.slots.iter().position(|s| *s == Some(sym))
+    }
+
+    fn rows_of(&self, sym: char) -> Option<[u8; 8]> {
+        self.glyphs.iter().find(|(c, _)| *c == sym).map(|(_, rows)| *rows)
+    }
+
+    fn tick(&mut self) -> u32 {
+        self.clock = self.clock.wrapping_add(1);
+        self.clock
+    }
+}
+
+fn cgram_slot(code: u8) -> Option<u8> { // ← 0x08-0x0F mirror 0x00-0x07
+    (code < 0x10).then_some(code & 0x7)
+}
@@ -2,12 +2,15 @@
 //                better to make separate modules (e.g. ext, algo) n' such. Fix this later perhaps.
 //                (or never. Up to you ya lovely programmer ^^)
 
-use crate::bitops::{comp24, decomp24};
-use crate::gsearch::ext_dm;
+use flcavr2::bitops::{comp24, decomp24};
+use flcavr2::gsearch::ext_dm;
 use crate::DeliveryStatus;
 use arduino_hal::eeprom::OutOfBoundsError;
 use arduino_hal::Eeprom;
-use fchashmap::FcHashMap;
+use flcavr2::cgrom::CgRom;
+use flcavr2::translit;
+use flcavr2::log;
+use ufmt::uWrite;
 // trait Bytable {
 //     fn bytize(&self) -> &[u8];
 //     fn debytize(byt: &[u8]) -> Self;
@@ -156,70 +159,70 @@ impl RelAddressPointer {
     }
 }
 
-pub struct EntryManager {
+pub struct EntryManager<L> { // ← L = log sink (see log.rs)
     pre_pointer: AddressPointer, // Open write addr; addresses first 3 sectors (0x0-0xBFF or 0-3071)
     post_pointer: AddressPointer, // Open write addr; addresses 90% of last sector (0xC00-0xF9B or 3072-3995)
     eeprw: Eeprom,
-    ecounter: u8
-    //pub(crate) serial: Usart<USART0, Pin<Input, PE0>, Pin<Output, PE1>> // evil floating point bit level hacking—— sorry, actually just me not understanding basic Rust lifetimes/borrowing lol -△-
+    ecounter: u8,
+    log: L // ← a log::Tap on main's serial, so the port isn't ours to keep
 }
 
-impl EntryManager {
-    pub fn new(eeprw: Eeprom) -> Self {
+impl<L: uWrite> EntryManager<L> {
+    pub fn new(eeprw: Eeprom, log: L) -> Self {
         Self {
             pre_pointer: AddressPointer::default(0x0, 0xBFF),
             post_pointer: AddressPointer::default(0xC00, 0xF9B),
             eeprw,
-            ecounter: 0
-           // serial
+            ecounter: 0,
+            log
         }
     }
 
     // FIXME
-    pub fn load_sample(&mut self, mapper: &FcHashMap<char, u8, 256>) {
-        let p0 = const_dat(0, 0, 0, "Chamomile please! Keep warm.", mapper);
+    pub fn load_sample(&mut self, rom: CgRom) { // ← descriptions are stored pre-mapped, so they're tied to the target panel's ROM
+        let p0 = const_dat(0, 0, 0, "Chamomile please! Keep warm.", rom);
         self.write_pre(&p0);
 
         // let b0 = transmute_dat(&p0, 0, 0, DeliveryStatus::Absent);
         // self.write_post(&b0);
 
-        // let p1 = const_dat(1, 0, 0, "Keep warm; steady.", mapper);
+        // let p1 = const_dat(1, 0, 0, "Keep warm; steady.", rom);
         // self.write_pre(&p1);
         //
-        // let p2 = const_dat(2, 0, 0, "₤20 continental.", mapper);
+        // let p2 = const_dat(2, 0, 0, "₤20 continental.", rom);
         // self.write_pre(&p2);
         //
-        // let p3 = const_dat(3, 0, 0, "No almonds, add utensils.", mapper);
+        // let p3 = const_dat(3, 0, 0, "No almonds, add utensils.", rom);
         // self.write_pre(&p3);
         //
-        // let p4 = const_dat(4, 0, 0, "Patient discharged, void.", mapper);
+        // let p4 = const_dat(4, 0, 0, "Patient discharged, void.", rom);
         // self.write_pre(&p4);
         //
-        // let p5 = const_dat(5, 0, 0, "Veranda package to-go.", mapper);
+        // let p5 = const_dat(5, 0, 0, "Veranda package to-go.", rom);
         // self.write_pre(&p5);
 
-        // let p6 = const_dat(6, 0, 0, "ニヲサーネロ。", mapper);
+        // let p6 = const_dat(6, 0, 0, "ニヲサーネロ。", rom);
         // self.write_pre(&p6);
         //
-        // let p7 = const_dat(7, 0, 0, "N/A", mapper);
+        // let p7 = const_dat(7, 0, 0, "N/A", rom);
         // self.write_pre(&p7);
         //
-        // let p8 = const_dat(8, 0, 0, "Shellfish allergy, fragile.", mapper);
+        // let p8 = const_dat(8, 0, 0, "Shellfish allergy, fragile.", rom);
         // self.write_pre(&p8);
 
-        // let p9 = const_dat(9, 0, 0, "Hand deliver triple-wrapped.", mapper);
+        // let p9 = const_dat(9, 0, 0, "Hand deliver triple-wrapped.", rom);
         // self.write_pre(&p9);
     }
 
     // TODO FIX V
-    pub fn read_pre(&mut self, index: u8, v: u8) -> (&str, [u8; 28], u8, u8) { // dictname, description, CGROM symbol, distance
-        let addr = self.pre_pointer.lbound + (index * 8) as u16;
+    pub fn read_pre(&mut self, index: u8, v: u8) -> (&str, [u8; 252], u8, u8) { // dictname, description, CGROM symbol, distance
+        let addr = self.pre_pointer.lbound + index as u16 * 256; // ← one Preentry = 256B
         let mut buf = [0u8; 256];
         self.eeprw.read(addr, &mut buf).expect("help :(");
 
         let dictname = ROOM_DICT[v as usize];
-        let mut desc = [0u8; 28];
-        desc.copy_from_slice(&buf[4..32]);
+        let mut desc = [0u8; 252]; // ← whole thing; Layout::from_codes wraps/pages it and drops the space padding
+        desc.copy_from_slice(&buf[4..]);
 
 
         (dictname, desc, ext_dm(index as usize, v as usize, false), ext_dm(index as usize, v as usize, true))
@@ -233,7 +236,7 @@ impl EntryManager {
 
     fn write_pre(&mut self, pre: &Preentry) {
         // Allotted EEPROM space is first 3 sectors or 0x0-0xBFF.
-        eepwrite(&mut self.pre_pointer, &pre.bytize(), &mut self.eeprw);
+        eepwrite(&mut self.pre_pointer, &pre.bytize(), &mut self.eeprw, &mut self.log);
         self.ecounter += 1;
     }
 
@@ -247,33 +250,35 @@ impl EntryManager {
     }
 }
 
-fn eepwrite(ptr: &mut AddressPointer, buf: &[u8], eeprw: &mut Eeprom) {
+fn eepwrite<L: uWrite>(ptr: &mut AddressPointer, buf: &[u8], eeprw: &mut Eeprom, log: &mut L) {
     let offset = ptr.addr;
     let blen = buf.len() as u16;
 
     if offset < ptr.lbound || offset > (ptr.ubound - blen) {
-       // ufmt::uwriteln!(serial, "ABORT: EEPROM write @ {:#02x}-{:#02x} out of bounds.", offset, offset + blen - 1);
+        log!(*log, "ABORT: EEPROM write @ {}-{} out of bounds.", offset, offset + blen - 1);
     } else {
         if eeprw.read_byte(offset) != 0 { // NOTE: smallest chance of off-by-1 error or data may be intended to be 0 there. Fix if needed.
-            //ufmt::uwriteln!(serial, "WARN: EEPROM write @ {:#02x}-{:#02x} potentially overwriting data.", offset, offset + blen - 1);
+            log!(*log, "WARN: EEPROM write @ {}-{} potentially overwriting data.", offset, offset + blen - 1);
         }
 
         let status = eeprw.write(offset, buf);
         ptr.update(|a| a + offset).unwrap();
 
-        // match status {
-        //     Ok(_) => ufmt::uwriteln!(serial, "OK: EEPROM write @ {:#02x}-{:#02x} successful.", offset, offset + blen - 1).unwrap_infallible(),
-        //     Err(_) => ufmt::uwriteln!(serial, "ERR: EEPROM write @ {:#02x}-{:#02x} failed.", offset, offset + blen - 1).unwrap_infallible(),
-        // }
+        match status {
+            Ok(_) => log!(*log, "OK: EEPROM write @ {}-{} successful.", offset, offset + blen - 1),
+            Err(_) => log!(*log, "ERR: EEPROM write @ {}-{} failed.", offset, offset + blen - 1)
+        }
     }
 }
 
-fn const_dat(dict: u8, ttd: u16, flags: u8, desc: &str, mapper: &FcHashMap<char, u8, 256>) -> Preentry {
+fn const_dat(dict: u8, ttd: u16, flags: u8, desc: &str, rom: CgRom) -> Preentry {
     Preentry { dict, ttd, flags, desc: {
-        let mut end = [0u8; 252];
+        let mut end = [b' '; 252]; // ← space padding; 0x00 is CGR_UP and would read back as a trailing ↑
+
+        let syms = desc.chars().flat_map(|c| translit::codes(c, rom, translit::default_replacement(rom)));
 
-        for (i,c) in desc.chars().take(252).enumerate() {
-            end[i] = *mapper.get(&c).unwrap_or(&0b1111_1111);
+        for (i, sym) in syms.take(252).enumerate() {
+            end[i] = sym;
         }
 
         end
@@ -0,0 +1,560 @@
+use alloc::string::String;
+use core::fmt;
+use ufmt::uWrite;
+use crate::cgram::GlyphBank;
+use crate::cgrom;
+use crate::cgrom::CgRom;
+use crate::geometry::Geometry;
+use crate::lcd1602::{cgram, ddram, exec_us, stream, HD44780Kernel, LcdError, Lcd1602, stock_bank};
+use crate::translit;
+
+// Software HD44780 for host builds. It takes the very same 10-bit register words the real backends put on the
+// wire (cmb) and replays them against a model of DDRAM, CGRAM, the address counter, entry mode and display
+// shift, so anything written against HD44780Util (affix, marque, flush, timer...) can be run on Linux and the
+// visible window read back as text with screen(). Time is simulated: dlm/dlu only advance a clock, and the
+// busy flag stays up for the datasheet execution time of the last instruction measured on that clock.
+
+const DDRAM_SIZE: usize = 0x80;
+const CGRAM_SIZE: usize = 0x40;
+const LINE: u8 = 40; // ← cells per DDRAM line in 2-line mode
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Target {
+    Ddram,
+    Cgram
+}
+
+pub struct EmuLcd1602 {
+    ddram: [u8; DDRAM_SIZE],
+    cgram: [u8; CGRAM_SIZE],
+    ac: u8,
+    target: Target,
+    id: bool,     // ← entry mode: AC increments (else decrements)
+    s: bool,      // ← entry mode: display shifts along with every write
+    shift: u8,    // ← how many cells the window has moved left, 0..40
+    display: bool,
+    cursor: bool,
+    blink: bool,
+    now_us: u64,
+    busy_until: u64,
+    glyphs: GlyphBank,
+    rom: CgRom,
+    replacement: u8,
+    geometry: Geometry
+}
+
+impl Default for EmuLcd1602 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EmuLcd1602 {
+    pub fn new() -> Self { // ← power-on state, before init() (datasheet "Initializing by Internal Reset Circuit")
+        Self {
+            ddram: [0x20; DDRAM_SIZE],
+            cgram: [0; CGRAM_SIZE],
+            ac: 0,
+            target: Target::Ddram,
+            id: true,
+            s: false,
+            shift: 0,
+            display: false,
+            cursor: false,
+            blink: false,
+            now_us: 0,
+            busy_until: 0,
+            glyphs: stock_bank(),
+            rom: CgRom::A00,
+            replacement: translit::default_replacement(CgRom::A00),
+            geometry: Geometry::LCD1602
+        }
+    }
+
+    pub fn now_ms(&self) -> u32 { // ← simulated time since new(); handy as the now_ms for Marquee::tick
+        (self.now_us / 1000) as u32
+    }
+
+    pub fn cursor_at(&self) -> Option<(u8, u8)> { // ← (row, col) of the visible cursor, if it's on and in the window
+        if !self.display || !(self.cursor || self.blink) || self.target != Target::Ddram {
+            return None;
+        }
+
+        (0..self.geometry.rows).find_map(|row| (0..self.geometry.cols).find(|&col| self.window(row, col) == self.ac).map(|col| (row, col)))
+    }
+
+    pub fn cell(&self, row: u8, col: u8) -> u8 { // ← raw code shown at (row, col), display shift included
+        self.ddram[self.window(row, col) as usize]
+    }
+
+    pub fn row(&self, row: u8) -> String {
+        (0..self.geometry.cols).map(|col| if self.display { self.glyph(self.cell(row, col)) } else { ' ' }).collect()
+    }
+
+    pub fn screen(&self) -> String { // ← every visible row, '\n'-separated
+        let mut screen = String::new();
+
+        for row in 0..self.geometry.rows {
+            if row > 0 {
+                screen.push('\n');
+            }
+            screen.push_str(&self.row(row));
+        }
+
+        screen
+    }
+
+    pub fn cgrows(&self, slot: u8) -> [u8; 8] { // ← what the controller really holds, not what the bank thinks it does
+        let base = (slot as usize & 0x7) << 3;
+        let mut rows = [0u8; 8];
+        rows.copy_from_slice(&self.cgram[base..base + 8]);
+        rows
+    }
+
+    fn glyph(&self, code: u8) -> char {
+        match code {
+            0x00..=0x0F => self.glyphs.resident(code).unwrap_or('?'), // ← CGRAM; only the bank knows what it's meant to be
+            _ => cgrom::reverse(code, self.rom).unwrap_or('?')
+        }
+    }
+
+    fn window(&self, row: u8, col: u8) -> u8 { // ← DDRAM address under a visible cell
+        let base = self.geometry.bases[row.min(self.geometry.rows - 1) as usize];
+        let line = base & 0x40;
+        line | (((base & 0x3F) + self.shift + col) % LINE)
+    }
+
+    fn step(&mut self, up: bool) { // ← AC auto-increment/decrement, wrapping the way the silicon does
+        self.ac = match (self.target, up) {
+            (Target::Cgram, true) => (self.ac + 1) & 0x3F,
+            (Target::Cgram, false) => self.ac.wrapping_sub(1) & 0x3F,
+            (Target::Ddram, true) => match self.ac {
+                0x27 => 0x40,
+                0x67 => 0x00,
+                ac => ac + 1
+            },
+            (Target::Ddram, false) => match self.ac {
+                0x00 => 0x67,
+                0x40 => 0x27,
+                ac => ac - 1
+            }
+        };
+    }
+
+    fn scroll(&mut self, left: bool) {
+        self.shift = if left { (self.shift + 1) % LINE } else { (self.shift + LINE - 1) % LINE };
+    }
+
+    fn exec(&mut self, reg: &u16) { // ← one instruction, decoded exactly like datasheet Table 6
+        let data = (reg & 0xFF) as u8;
+
+        match reg >> 8 {
+            0b10 => {
+                match self.target {
+                    Target::Ddram => self.ddram[self.ac as usize] = data,
+                    Target::Cgram => self.cgram[self.ac as usize] = data & 0x1F
+                }
+                self.step(self.id);
+
+                if self.s && self.target == Target::Ddram {
+                    self.scroll(self.id);
+                }
+            }
+            0b00 => match data.leading_zeros() {
+                0 => { self.ac = data & 0x7F; self.target = Target::Ddram; }
+                1 => { self.ac = data & 0x3F; self.target = Target::Cgram; }
+                2 => {} // ← function set; the model is always 2-line, 5x8
+                3 => {
+                    let rl = data & 0b0100 != 0;
+                    if data & 0b1000 != 0 {
+                        self.scroll(!rl);
+                    } else {
+                        self.step(rl);
+                    }
+                }
+                4 => { self.display = data & 0b100 != 0; self.cursor = data & 0b10 != 0; self.blink = data & 0b1 != 0; }
+                5 => { self.id = data & 0b10 != 0; self.s = data & 0b1 != 0; }
+                6 => { self.ac = 0; self.shift = 0; self.target = Target::Ddram; }
+                7 => {
+                    self.ddram = [0x20; DDRAM_SIZE];
+                    self.ac = 0;
+                    self.shift = 0;
+                    self.id = true; // ← clear also resets I/D (but leaves S alone)
+                    self.target = Target::Ddram;
+                }
+                _ => {} // ← all zeroes isn't an instruction
+            },
+            _ => {} // ← reads go through rdb/dtr/rac
+        }
+
+        self.busy_until = self.now_us + exec_us(reg) as u64;
+    }
+}
+
+impl HD44780Kernel for EmuLcd1602 {
+    fn polls(&self) -> bool {
+        true
+    }
+
+    fn enp(&mut self) -> Result<(), LcdError> {
+        Ok(()) // ← cmb hands over whole words; there's no bus to strobe
+    }
+
+    fn cmd(&mut self, reg: &u16) -> Result<(), LcdError> {
+        self.cmb(reg)?;
+        self.bus(reg)
+    }
+
+    fn cmb(&mut self, reg: &u16) -> Result<(), LcdError> {
+        if self.rdb()? {
+            return Err(LcdError::BusyTimeout); // ← a real panel would drop (or mangle) it; better to hear about it here
+        }
+
+        self.exec(reg);
+        Ok(())
+    }
+
+    fn rdb(&mut self) -> Result<bool, LcdError> {
+        Ok(self.now_us < self.busy_until)
+    }
+
+    fn clr(&mut self) -> Result<(), LcdError> {
+        self.cmd(&0b00_0000_0001)?;
+        self.glyphs.release();
+        Ok(())
+    }
+
+    fn ret(&mut self) -> Result<(), LcdError> {
+        self.cmd(&0b00_0000_0010)
+    }
+
+    fn ems(&mut self, id: bool, s: bool) -> Result<(), LcdError> {
+        self.cmd(&(0b00_0000_0100 | ((id as u16) << 1) | (s as u16)))
+    }
+
+    fn dsw(&mut self, d: bool, c: bool, b: bool) -> Result<(), LcdError> {
+        self.cmd(&(0b00_0000_1000 | ((d as u16) << 2) | ((c as u16) << 1) | (b as u16)))
+    }
+
+    fn cds(&mut self, sc: bool, rl: bool) -> Result<(), LcdError> {
+        self.cmd(&(0b00_0001_0000 | ((sc as u16) << 3) | ((rl as u16) << 2)))
+    }
+
+    fn fns(&mut self, dl: bool, n: bool, f: bool) -> Result<(), LcdError> {
+        self.cmd(&(0b00_0010_0000 | ((dl as u16) << 4) | ((n as u16) << 3) | ((f as u16) << 2)))
+    }
+
+    fn cgs(&mut self, addr: u8) -> Result<(), LcdError> {
+        self.cmd(&(0b00_0100_0000 | cgram(addr)?))
+    }
+
+    fn dds(&mut self, addr: u8) -> Result<(), LcdError> {
+        self.cmd(&(0b00_1000_0000 | ddram(addr)?))
+    }
+
+    fn dtw(&mut self, data: u8) -> Result<(), LcdError> {
+        self.cmd(&(0b10_0000_0000 | (data as u16)))
+    }
+
+    fn dtr(&mut self) -> Result<u8, LcdError> {
+        if self.rdb()? {
+            return Err(LcdError::BusyTimeout);
+        }
+
+        let data = match self.target {
+            Target::Ddram => self.ddram[self.ac as usize],
+            Target::Cgram => self.cgram[self.ac as usize]
+        };
+        self.step(self.id);
+        self.busy_until = self.now_us + exec_us(&0b11_0000_0000) as u64;
+
+        self.bus(&0b11_0000_0000)?;
+        Ok(data)
+    }
+
+    fn boot(&mut self) -> Result<(), LcdError> {
+        self.dlm(150);
+        self.cmd(&0b00_0011_1000) // DL=8D, N=2R, F=5x7; the handshake nibbles only matter on real wires
+    }
+
+    fn dlm(&mut self, ms: u32) {
+        self.now_us += ms as u64 * 1000;
+    }
+
+    fn dlu(&mut self, us: u32) {
+        self.now_us += us as u64;
+    }
+
+    fn rac(&mut self) -> Result<u8, LcdError> {
+        Ok(self.ac)
+    }
+}
+
+impl Lcd1602 for EmuLcd1602 {
+    fn state(&mut self) -> (&mut GlyphBank, CgRom, u8) {
+        (&mut self.glyphs, self.rom, self.replacement)
+    }
+
+    fn set_rom(&mut self, rom: CgRom) {
+        self.rom = rom;
+        self.replacement = translit::default_replacement(rom);
+    }
+
+    fn set_replacement(&mut self, sym: u8) {
+        self.replacement = sym;
+    }
+
+    fn geometry(&self) -> Geometry {
+        self.geometry
+    }
+
+    fn set_geometry(&mut self, geometry: Geometry) {
+        self.geometry = geometry;
+    }
+}
+
+impl uWrite for EmuLcd1602 {
+    type Error = LcdError;
+
+    fn write_str(&mut self, s: &str) -> Result<(), LcdError> {
+        stream(self, s)
+    }
+}
+
+impl fmt::Write for EmuLcd1602 {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        stream(self, s).map_err(|_| fmt::Error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use core::fmt::Write;
+    use ufmt::uwrite;
+    use crate::bignum;
+    use crate::frame::Frame;
+    use crate::geometry::Geometry;
+    use crate::lcd1602::{HD44780Kernel, HD44780Util, LcdError, Lcd1602};
+    use super::EmuLcd1602;
+
+    fn lcd() -> EmuLcd1602 {
+        let mut lcd = EmuLcd1602::new();
+        lcd.init().unwrap();
+        lcd.ems(true, false).unwrap(); // ← init leaves S=1; most tests want the window to hold still
+        lcd
+    }
+
+    #[test]
+    fn affix() {
+        let mut lcd = lcd();
+        lcd.affix(0, "Init OK!").unwrap();
+        lcd.affix(1, "C148 ↑ 12m").unwrap();
+        assert_eq!(lcd.screen(), "Init OK!        \nC148 ↑ 12m      ");
+    }
+
+    #[test]
+    fn affix_transliterates() {
+        let mut lcd = lcd();
+        assert_eq!(lcd.affix(0, "Café"), Ok(()));
+        assert_eq!(lcd.row(0), "Cafe            ");
+    }
+
+    #[test]
+    fn entry_shift() { // ← S=1 (what init leaves): every write moves the window along, the cursor stays put on screen
+        let mut lcd = EmuLcd1602::new();
+        lcd.init().unwrap();
+        lcd.dsw(true, true, false).unwrap();
+        lcd.affix(0, "AB").unwrap();
+        assert_eq!(lcd.cursor_at(), Some((0, 0)));
+        assert_eq!(lcd.row(0), "                ");
+        lcd.ret().unwrap();
+        assert_eq!(lcd.row(0), "AB              ");
+    }
+
+    #[test]
+    fn cursor_and_display_shift() {
+        let mut lcd = lcd();
+        lcd.dsw(true, true, false).unwrap();
+        lcd.affix(0, "HELLO").unwrap();
+        assert_eq!(lcd.cursor_at(), Some((0, 5)));
+
+        lcd.cds(false, false).unwrap(); // ← cursor one left
+        assert_eq!(lcd.cursor_at(), Some((0, 4)));
+
+        lcd.cds(true, false).unwrap(); // ← display one left
+        assert_eq!(lcd.row(0), "ELLO            ");
+        assert_eq!(lcd.cursor_at(), Some((0, 3)));
+
+        lcd.cds(true, true).unwrap();
+        lcd.cds(true, true).unwrap(); // ← and two right; the hidden end of the line scrolls in
+        assert_eq!(lcd.row(0), " HELLO          ");
+    }
+
+    #[test]
+    fn marque() {
+        let mut lcd = lcd();
+        lcd.affix(0, "HELLO").unwrap();
+        lcd.affix(1, "world").unwrap();
+        let start = lcd.screen();
+
+        lcd.marque(1, false).unwrap(); // ← a full lap of the 40-cell line comes back to where it started
+        assert_eq!(lcd.screen(), start);
+    }
+
+    #[test]
+    fn marque_midway() {
+        let mut lcd = lcd();
+        lcd.affix(0, "HELLO").unwrap();
+        for _ in 0..3 {
+            lcd.cds(true, true).unwrap();
+        }
+        assert_eq!(lcd.row(0), "   HELLO        ");
+    }
+
+    #[test]
+    fn flush() {
+        let mut lcd = lcd();
+        let mut frame = Frame::new(Geometry::LCD1602);
+        lcd.paint(&mut frame, "ward C148").unwrap();
+        frame.at(1, 10);
+        lcd.paint(&mut frame, "→ 12m").unwrap();
+        lcd.flush(&mut frame).unwrap();
+        assert_eq!(lcd.screen(), "ward C148       \n          → 12m ");
+
+        frame.at(1, 12);
+        lcd.paint(&mut frame, "9m ").unwrap();
+        lcd.flush(&mut frame).unwrap();
+        assert_eq!(lcd.row(1), "          → 9m  ");
+    }
+
+    #[test]
+    fn flush_undoes_entry_shift() {
+        let mut lcd = EmuLcd1602::new();
+        lcd.init().unwrap(); // ← S=1 left on; flush has to cope
+        let mut frame = Frame::new(Geometry::LCD1602);
+        lcd.paint(&mut frame, "steady").unwrap();
+        lcd.flush(&mut frame).unwrap();
+        assert_eq!(lcd.row(0), "steady          ");
+    }
+
+    #[test]
+    fn flush_recycles_glyphs() { // ← no clr() ever; a glyph that's left the frame gives its slot back
+        let mut lcd = lcd();
+        let mut frame = Frame::new(Geometry::LCD1602);
+        for c in "▏▎▍▌█▁▂▃▄▅▆▇".chars() {
+            frame.clear();
+            lcd.paint(&mut frame, c.encode_utf8(&mut [0u8; 4])).unwrap();
+            lcd.flush(&mut frame).unwrap();
+            assert_eq!(lcd.row(0).chars().next(), Some(c));
+        }
+    }
+
+    #[test]
+    fn flush_keeps_evicted_glyphs() {
+        let mut lcd = lcd();
+        let mut frame = Frame::new(Geometry::LCD1602);
+        lcd.paint(&mut frame, "▏").unwrap();
+        lcd.flush(&mut frame).unwrap();
+
+        lcd.glyphs().forget(6); // ← e.g. a Mirror rewrote it
+        frame.at(1, 0);
+        lcd.paint(&mut frame, "▎").unwrap(); // ← lands in slot 6 again, so "▏" moves out
+        lcd.flush(&mut frame).unwrap();
+        assert_eq!(lcd.screen(), "▏               \n▎               ");
+    }
+
+    #[test]
+    fn timer() {
+        let mut lcd = lcd();
+        lcd.timer("Tea!", 3).unwrap();
+
+        let big = bignum::compose("00:01"); // ← last tick shows 00:01 with the colon lit
+        let pad = (16 - big.width as usize) / 2;
+        let row = |s: &str| {
+            let mut row = String::from(" ").repeat(pad);
+            row.push_str(s);
+            while row.chars().count() < 16 {
+                row.push(' ');
+            }
+            row
+        };
+
+        assert_eq!(lcd.row(0), row(&big.top));
+        assert_eq!(lcd.row(1), row(&big.bottom));
+        assert!(lcd.now_ms() >= 2000 + 3 * 670);
+    }
+
+    #[test]
+    fn timer_past_unmappable() { // ← the countdown still runs to the end; the miss is reported afterwards
+        let mut lcd = lcd();
+        assert_eq!(lcd.timer("Tea ☃", 3), Err(LcdError::Unmappable('☃')));
+
+        let mut clean = self::lcd();
+        clean.timer("Tea!", 3).unwrap();
+        assert_eq!(lcd.screen(), clean.screen());
+        assert_eq!(lcd.now_ms(), clean.now_ms());
+    }
+
+    #[test]
+    fn one_cell_many_glyphs() { // ← each glyph overwrites the last, so its slot is free for the next one
+        let mut lcd = lcd();
+        for c in "▏▎▍▌█▁▂▃▄▅▆▇".chars() {
+            let mut buf = [0; 4];
+            lcd.affix(0, c.encode_utf8(&mut buf)).unwrap();
+            assert!(lcd.row(0).starts_with(c));
+        }
+    }
+
+    #[test]
+    fn stream() { // ← write!/uwrite! go through disp_char: transliterated, replaced, and on along DDRAM
+        let mut lcd = lcd();
+        uwrite!(lcd, "{}°C ☃", 21).unwrap(); // ← ☃ comes out as the replacement (A00's 0xFF) and doesn't stop the rest
+        write!(lcd, "{:>4}", "ok").unwrap();
+        assert_eq!(lcd.row(0), "21°C ▓  ok      ");
+
+        lcd.dds(0x00).unwrap();
+        write!(lcd, "{:40}é", "").unwrap(); // ← the 41st cell wraps onto the second line
+        assert_eq!(lcd.row(1), "e               ");
+    }
+
+    #[test]
+    fn timer_on_four_rows() {
+        let mut lcd = EmuLcd1602::new();
+        lcd.set_geometry(Geometry::LCD2004);
+        lcd.init().unwrap();
+        lcd.ems(true, false).unwrap();
+        lcd.timer("Tea for C148", 1).unwrap();
+        assert_eq!(lcd.row(2), "Tea for C148        ");
+        assert_eq!(lcd.row(3), "                    ");
+    }
+
+    #[test]
+    fn bsd() {
+        let mut lcd = lcd();
+        lcd.affix(0, "header").unwrap();
+        lcd.affix(1, "ABCDEFGHIJKLMNOP").unwrap();
+
+        lcd.bsd(1, 2, false).unwrap();
+        assert_eq!(lcd.screen(), "header          \n  ABCDEFGHIJKLMN");
+
+        lcd.bsd(1, -4, false).unwrap(); // ← O and P were only hidden (the line is 40 cells), A and B fall off for good
+        assert_eq!(lcd.row(1), "CDEFGHIJKLMNOP  ");
+
+        lcd.bsd(1, 2, false).unwrap();
+        assert_eq!(lcd.row(1), "  CDEFGHIJKLMNOP");
+    }
+
+    #[test]
+    fn bso_laps() {
+        let mut lcd = lcd();
+        lcd.affix(0, "header").unwrap();
+        lcd.affix(1, "ABCD").unwrap();
+
+        lcd.bso(1, false).unwrap(); // ← leftward wraps round the hidden part of the line
+        assert_eq!(lcd.row(1), "BCD             ");
+
+        lcd.ls(1, true, 10, 1).unwrap(); // ← a whole lap lands back on the shifted row
+        assert_eq!(lcd.screen(), "header          \nBCD             ");
+    }
+}
@@ -0,0 +1,152 @@
+use crate::cgram::CGRAM_SLOTS;
+use crate::geometry::{Geometry, MAX_CELLS};
+
+// Off-screen copy of the panel. Draw into it as much as you like (HD44780Util::paint), then HD44780Util::flush
+// sends only the cells that differ from what the controller is known to hold. Cells are CGROM codes, not chars,
+// so transliteration and glyph uploads already happened by the time anything is diffed.
+
+const BLANK: u8 = 0b0010_0000; // ← ' '
+const BRIDGE: u8 = 1;          // ← clean cells worth rewriting to skip a DDRAM address set (1 cell = 1 dtw = 1 dds)
+
+pub struct Frame {
+    geometry: Geometry,
+    cells: [u8; MAX_CELLS],  // ← what the app wants on screen, row-major
+    shadow: [u8; MAX_CELLS], // ← what DDRAM holds as of the last flush
+    glyphs: [Option<char>; CGRAM_SLOTS], // ← which char each CGRAM code meant when it was painted
+    synced: bool,            // ← false → shadow is junk, next flush redraws everything
+    row: u8,
+    col: u8,                 // ← where the next put lands, and where the hardware cursor gets parked
+    cursor: (bool, bool),    // ← (underline, blink) wanted
+    shown: (bool, bool)      // ← (underline, blink) as last sent
+}
+
+impl Frame {
+    pub fn new(geometry: Geometry) -> Self {
+        Self {
+            geometry,
+            cells: [BLANK; MAX_CELLS],
+            shadow: [BLANK; MAX_CELLS],
+            glyphs: [None; CGRAM_SLOTS],
+            synced: false,
+            row: 0,
+            col: 0,
+            cursor: (false, false),
+            shown: (false, false)
+        }
+    }
+
+    pub fn geometry(&self) -> Geometry {
+        self.geometry
+    }
+
+    pub fn at(&mut self, row: u8, col: u8) {
+        self.row = row.min(self.geometry.rows - 1);
+        self.col = col.min(self.geometry.cols);
+    }
+
+    pub fn put(&mut self, code: u8) { // ← write at the cursor and advance; anything past the row's end is clipped
+        if self.col < self.geometry.cols {
+            let i = self.index(self.row, self.col);
+            self.cells[i] = code;
+            self.col += 1;
+        }
+
+        if code < 0x10 {
+            self.glyphs[code as usize & 0x7] = None; // ← no idea which char the caller meant; paint notes it again
+        }
+    }
+
+    pub fn clear(&mut self) { // ← blank every cell; only costs bus time for the ones that weren't blank already
+        self.cells = [BLANK; MAX_CELLS];
+        self.row = 0;
+        self.col = 0;
+    }
+
+    pub fn cursor(&mut self, underline: bool, blink: bool) {
+        self.cursor = (underline, blink);
+    }
+
+    pub fn invalidate(&mut self) { // ← someone else touched DDRAM (clr, affix, marque...); trust nothing
+        self.synced = false;
+    }
+
+    pub fn cell(&self, row: u8, col: u8) -> u8 {
+        self.cells[self.index(row, col)]
+    }
+
+    pub fn position(&self) -> (u8, u8) {
+        (self.row, self.col)
+    }
+
+    fn index(&self, row: u8, col: u8) -> usize {
+        row as usize * self.geometry.cols as usize + col as usize
+    }
+
+    pub(crate) fn synced(&self) -> bool {
+        self.synced
+    }
+
+    pub(crate) fn run(&self, row: u8, from: u8) -> Option<(u8, u8)> { // ← next [start, end) of cells to send on this row
+        let cols = self.geometry.cols;
+        let dirty = |col: u8| {
+            let i = self.index(row, col);
+            !self.synced || self.cells[i] != self.shadow[i]
+        };
+
+        let start = (from..cols).find(|&col| dirty(col))?;
+        let mut end = start + 1;
+
+        while end < cols {
+            match (end..cols).take(BRIDGE as usize + 1).position(&dirty) {
+                Some(gap) => end += gap as u8 + 1,
+                None => break
+            }
+        }
+
+        Some((start, end))
+    }
+
+    pub(crate) fn commit(&mut self, row: u8, start: u8, end: u8) {
+        let (from, to) = (self.index(row, start), self.index(row, end));
+        self.shadow[from..to].copy_from_slice(&self.cells[from..to]);
+    }
+
+    pub(crate) fn cursor_change(&self) -> Option<(bool, bool)> {
+        if !self.synced || self.cursor != self.shown {
+            Some(self.cursor)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn slots(&self) -> u8 { // ← bitmask of the CGRAM slots the cells draw from
+        self.visible().filter(|&code| code < 0x10).fold(0, |mask, code| mask | (1 << (code & 0x7)))
+    }
+
+    pub(crate) fn remap(&mut self, moves: &[(u8, u8)]) { // ← (slot, code) pairs: the cells' glyph lives elsewhere now. All at once, so swaps can't chain
+        let len = self.geometry.rows as usize * self.geometry.cols as usize;
+        for cell in self.cells[..len].iter_mut().filter(|cell| **cell < 0x10) {
+            if let Some(&(_, code)) = moves.iter().find(|(slot, _)| *slot == *cell & 0x7) {
+                *cell = code;
+            }
+        }
+    }
+
+    pub(crate) fn meant(&self, slot: u8) -> Option<char> {
+        self.glyphs[slot as usize & 0x7]
+    }
+
+    pub(crate) fn note(&mut self, glyphs: [Option<char>; CGRAM_SLOTS]) { // ← what the slots hold right after a paint
+        self.glyphs = glyphs;
+    }
+
+    fn visible(&self) -> impl Iterator<Item = u8> + '_ {
+        let len = self.geometry.rows as usize * self.geometry.cols as usize;
+        self.cells[..len].iter().copied()
+    }
+
+    pub(crate) fn settle(&mut self) { // ← flush is done; shadow now mirrors DDRAM
+        self.shown = self.cursor;
+        self.synced = true;
+    }
+}
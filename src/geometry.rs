@@ -0,0 +1,33 @@
+// Where each visible row starts in DDRAM. The controller itself only knows two 40-cell lines (0x00-0x27 and
+// 0x40-0x67, 2-line mode); 4-row panels just fold each of them in half, so row 2 continues row 0 and row 3
+// continues row 1. That's also why a display shift drags rows 0+2 (and 1+3) around together.
+
+pub const MAX_ROWS: usize = 4;
+pub const MAX_CELLS: usize = 80; // ← all of DDRAM; 20x4 and 40x2 use every last cell
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Geometry {
+    pub cols: u8,
+    pub rows: u8,
+    pub bases: [u8; MAX_ROWS] // ← DDRAM address of each row's first cell (unused rows ignored)
+}
+
+impl Geometry {
+    pub const LCD1602: Geometry = Geometry { cols: 16, rows: 2, bases: [0x00, 0x40, 0x00, 0x00] };
+    pub const LCD1604: Geometry = Geometry { cols: 16, rows: 4, bases: [0x00, 0x40, 0x10, 0x50] };
+    pub const LCD2004: Geometry = Geometry { cols: 20, rows: 4, bases: [0x00, 0x40, 0x14, 0x54] };
+    pub const LCD4002: Geometry = Geometry { cols: 40, rows: 2, bases: [0x00, 0x40, 0x00, 0x00] };
+
+    pub fn addr(&self, row: u8, col: u8) -> u8 { // ← out-of-range rows land on the last one rather than in CGRAM
+        self.bases[row.min(self.rows - 1) as usize] + col
+    }
+
+    pub fn lane(&self, row: u8) -> (u8, u8) { // ← (first address, length) of the DDRAM a row has to itself, hidden cells included
+        let base = self.addr(row, 0);
+        if self.rows > 2 { (base, self.cols) } else { (base, self.span()) } // ← folded rows share their line with a sibling
+    }
+
+    pub fn span(&self) -> u8 { // ← cells per DDRAM line, i.e. how many shifts until a marquee comes full circle
+        40
+    }
+}
@@ -2,7 +2,9 @@ use alloc::vec;
 use alloc::vec::Vec;
 use core::cmp::Reverse;
 use priority_queue::PriorityQueue;
+use ufmt::uWrite;
 use crate::hash::NaiveXORHasherBuilder;
+use crate::{log, trace};
 // Due to inability in instantiating a static map (phf has >1 access issues, lazy_static and hashbrown do not compile since RISC-based AVR lacks the instructions to run the spin crate)
 // ...and AVR-HAL has no functionality for writing separate data to flash (cannot read during runtime anyway)
 // ...instead load another FcHashMap into SRAM.
@@ -23,17 +25,18 @@ use crate::hash::NaiveXORHasherBuilder;
 // CGR6 = DOWN_LEFT
 // 0b0111_1110 = RIGHT
 // 0b0111_1111 = LEFT
+// (CGRAM glyphs themselves live in lcd1602::CGRAM_ARROWS and are uploaded on init.)
 
 // TODO investigate if u8 is feasible (distances + priority?)
 
-const CGR_UP: u8 = 0b0000_0000;
-const CGR_DOWN: u8 = 0b0000_0001;
-const CGR_LEFT: u8 = 0b0111_1110;
-const CGR_RIGHT: u8 = 0b0111_1111;
-const CGR_UPLEFT: u8 = 0b0000_0010;
-const CGR_DOWNRIGHT: u8 = 0b0000_0011;
-const CGR_UPRIGHT: u8 = 0b0000_0100;
-const CGR_DOWNLEFT: u8 = 0b0000_0101;
+pub const CGR_UP: u8 = 0b0000_0000;
+pub const CGR_DOWN: u8 = 0b0000_0001;
+pub const CGR_LEFT: u8 = 0b0111_1111;  // ← CGROM '←' (was swapped with RIGHT)
+pub const CGR_RIGHT: u8 = 0b0111_1110; // ← CGROM '→'
+pub const CGR_UPLEFT: u8 = 0b0000_0010;
+pub const CGR_DOWNRIGHT: u8 = 0b0000_0011;
+pub const CGR_UPRIGHT: u8 = 0b0000_0100;
+pub const CGR_DOWNLEFT: u8 = 0b0000_0101;
 
 const EMPTY_NODE: Node = Node{ dm_index: 0, graph_index: 0 };
 
@@ -138,7 +141,7 @@ pub fn k_dijkstra(inds: Vec<u8>) -> (Vec<u8>, Vec<Node>) { // <-- use u8 index o
 // Held-Karp (exact but slooow), Lin-Kernighan (slower than 2-O but OK for symm)
 // Use Two-Opt based on https://or.stackexchange.com/questions/6764/is-there-a-ranking-of-heuristics-for-the-travelling-salesman-problem -> https://link.springer.com/article/10.1007/s00453-002-0986-1
 // You will get negative cost; this seems to be OK? Due to not using Euclidian distances but instead non-balanced metre weights.
-pub fn two_opt(tour: &mut [usize], max_iters: usize) {
+pub fn two_opt<L: uWrite>(tour: &mut [usize], max_iters: usize, log: &mut L) { // ← log: any sink (see log.rs); each swap is trace-only
     let n = tour.len();
     let mut improved = true;
     let mut iters = 0;
@@ -158,12 +161,15 @@ pub fn two_opt(tour: &mut [usize], max_iters: usize) {
                     swap_edges(tour, i, j);
                     cost += cost_delta;
                     improved = true;
+                    trace!(*log, "2OPT {} <> {} => {}", i, j, cost);
                 }
             }
         }
 
         iters += 1;
     }
+
+    log!(*log, "TOUR => {} after {} passes", cost, iters);
 }
 
 pub fn calc_tour_cost(tour: &[usize]) -> u32 {
@@ -1,5 +1,3 @@
-#![no_std]
-
 use core::hash::{BuildHasher, Hasher};
 
 // Step 1: Implement the Hasher
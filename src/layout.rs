@@ -0,0 +1,268 @@
+use alloc::vec::Vec;
+use crate::frame::Frame;
+
+// Greedy word wrap over CGROM codes. Works on codes rather than chars so pre-mapped EEPROM descriptions and
+// freshly encoded strings go through the same path, and so a char that transliterates to several cells
+// (… → ...) is measured at its real width and never split across two lines.
+
+const SPACE: u8 = 0b0010_0000;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Align {
+    Left,
+    Center,
+    Right
+}
+
+pub struct Layout {
+    codes: Vec<u8>,
+    lines: Vec<(u16, u8)>, // ← (offset into codes, length) per display line, trailing spaces trimmed
+    cols: u8,
+    align: Align,
+    fill: u8,              // ← what pads a line out to the full width
+    line_start: u16,
+    word_start: u16,       // ← last place a line may break without splitting a word
+    wrapped: bool          // ← current line exists because the previous one overflowed; eat its leading spaces
+}
+
+impl Layout {
+    pub fn new(cols: u8, align: Align) -> Self {
+        Self { codes: Vec::new(), lines: Vec::new(), cols, align, fill: SPACE, line_start: 0, word_start: 0, wrapped: false }
+    }
+
+    pub fn from_codes(codes: &[u8], cols: u8, align: Align) -> Self { // ← e.g. a Preentry desc; trailing space padding is dropped
+        let mut layout = Self::new(cols, align);
+        let len = codes.iter().rposition(|&c| c != SPACE).map_or(0, |i| i + 1); // ← not 0x00: that's CGRAM slot 0, the ↑ arrow
+
+        for &code in &codes[..len] {
+            match code {
+                SPACE => layout.space(),
+                _ => layout.push(&[code])
+            }
+        }
+
+        layout.finish();
+        layout
+    }
+
+    pub fn set_fill(&mut self, code: u8) {
+        self.fill = code;
+    }
+
+    pub fn push(&mut self, glyph: &[u8]) { // ← one char's worth of codes
+        let width = glyph.len() as u16;
+        if width == 0 {
+            return;
+        }
+
+        if self.used() + width > self.cols as u16 && self.word_start > self.line_start {
+            self.end_line(self.word_start); // ← the word moves down whole
+            self.line_start = self.word_start;
+            self.wrapped = true;
+        }
+
+        if self.used() + width > self.cols as u16 && self.used() > 0 {
+            self.end_line(self.len()); // ← word wider than the panel; nothing for it but to cut
+            self.line_start = self.len();
+            self.word_start = self.line_start;
+            self.wrapped = true;
+        }
+
+        self.codes.extend_from_slice(glyph);
+    }
+
+    pub fn space(&mut self) {
+        if self.wrapped && self.used() == 0 {
+            return;
+        }
+
+        if self.used() + 1 > self.cols as u16 {
+            self.end_line(self.len()); // ← the space itself is the break; no need to carry it over
+            self.line_start = self.len();
+            self.wrapped = true;
+        } else {
+            self.codes.push(SPACE);
+        }
+
+        self.word_start = self.len();
+    }
+
+    pub fn newline(&mut self) {
+        self.end_line(self.len());
+        self.line_start = self.len();
+        self.word_start = self.line_start;
+        self.wrapped = false;
+    }
+
+    pub fn finish(&mut self) { // ← close the last line; call once after the final push
+        if self.used() > 0 || self.lines.is_empty() {
+            self.end_line(self.len());
+        }
+
+        self.line_start = self.len();
+        self.word_start = self.line_start;
+    }
+
+    pub fn lines(&self) -> u8 {
+        self.lines.len() as u8
+    }
+
+    pub fn line(&self, i: u8) -> &[u8] {
+        match self.lines.get(i as usize) {
+            Some(&(start, len)) => &self.codes[start as usize..start as usize + len as usize],
+            None => &[]
+        }
+    }
+
+    pub fn pages(&self, rows: u8) -> u8 {
+        self.lines().div_ceil(rows).max(1)
+    }
+
+    pub fn render(&self, frame: &mut Frame, page: u8) { // ← paint one screenful (page wraps around) into the frame
+        let rows = frame.geometry().rows;
+        let cols = frame.geometry().cols.min(self.cols);
+        let first = (page % self.pages(rows)) * rows;
+
+        for row in 0..rows {
+            let line = self.line(first + row);
+            let len = (line.len() as u8).min(cols);
+            let lead = match self.align {
+                Align::Left => 0,
+                Align::Center => (cols - len) / 2,
+                Align::Right => cols - len
+            };
+
+            frame.at(row, 0);
+            for _ in 0..lead {
+                frame.put(self.fill);
+            }
+            for &code in &line[..len as usize] {
+                frame.put(code);
+            }
+            for _ in (lead + len)..frame.geometry().cols {
+                frame.put(self.fill);
+            }
+        }
+    }
+
+    fn end_line(&mut self, end: u16) {
+        let mut end = end;
+        while end > self.line_start && self.codes[end as usize - 1] == SPACE {
+            end -= 1;
+        }
+
+        self.lines.push((self.line_start, (end - self.line_start) as u8));
+    }
+
+    fn used(&self) -> u16 {
+        self.len() - self.line_start
+    }
+
+    fn len(&self) -> u16 {
+        self.codes.len() as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use super::{Align, Layout, SPACE};
+    use crate::frame::Frame;
+    use crate::geometry::Geometry;
+
+    fn layout(text: &str, cols: u8, align: Align) -> Layout { // ← ASCII codes are their own CGROM codes
+        let mut layout = Layout::new(cols, align);
+        for b in text.bytes() {
+            match b {
+                b' ' => layout.space(),
+                b'\n' => layout.newline(),
+                _ => layout.push(&[b])
+            }
+        }
+
+        layout.finish();
+        layout
+    }
+
+    fn lines(layout: &Layout) -> Vec<&[u8]> {
+        (0..layout.lines()).map(|i| layout.line(i)).collect()
+    }
+
+    fn row(frame: &Frame, row: u8) -> String {
+        (0..frame.geometry().cols).map(|col| frame.cell(row, col) as char).collect()
+    }
+
+    #[test]
+    fn wraps_whole_words() {
+        let layout = layout("the quick brown fox", 10, Align::Left);
+        assert_eq!(lines(&layout), [&b"the quick"[..], b"brown fox"]);
+    }
+
+    #[test]
+    fn break_eats_spaces() { // ← the space that overflows is the break; the ones after it don't start the next line
+        let layout = layout("abcde   fgh", 5, Align::Left);
+        assert_eq!(lines(&layout), [&b"abcde"[..], b"fgh"]);
+    }
+
+    #[test]
+    fn cuts_long_words() {
+        let layout = layout("a abcdefghijkl", 5, Align::Left);
+        assert_eq!(lines(&layout), [&b"a"[..], b"abcde", b"fghij", b"kl"]);
+    }
+
+    #[test]
+    fn keeps_glyphs_whole() { // ← … → "..." is one char; it moves down rather than splitting
+        let mut layout = Layout::new(4, Align::Left);
+        layout.push(b"a");
+        layout.push(b"b");
+        layout.push(b"...");
+        layout.finish();
+        assert_eq!(lines(&layout), [&b"ab"[..], b"..."]);
+    }
+
+    #[test]
+    fn newlines() {
+        let layout = layout("one\n\ntwo  \nthree", 16, Align::Left);
+        assert_eq!(lines(&layout), [&b"one"[..], b"", b"two", b"three"]);
+        assert_eq!(self::layout("", 16, Align::Left).lines(), 1); // ← still one (blank) line to show
+    }
+
+    #[test]
+    fn alignment() {
+        let mut frame = Frame::new(Geometry::LCD1602);
+        for (align, expected) in [(Align::Left, "abc             "), (Align::Center, "      abc       "), (Align::Right, "             abc")] {
+            let mut layout = layout("abc\nabcdefghijklmnop", 16, align);
+            layout.set_fill(b'.');
+            layout.render(&mut frame, 0);
+            assert_eq!(row(&frame, 0), expected.replace(' ', "."));
+            assert_eq!(row(&frame, 1), "abcdefghijklmnop");
+        }
+    }
+
+    #[test]
+    fn pages() {
+        let layout = layout("a\nb\nc", 16, Align::Left);
+        assert_eq!(layout.pages(2), 2);
+        assert_eq!(layout.pages(4), 1);
+
+        let mut frame = Frame::new(Geometry::LCD1602);
+        layout.render(&mut frame, 1);
+        assert_eq!(row(&frame, 0), "c               ");
+        assert_eq!(row(&frame, 1), "                "); // ← past the last line
+        layout.render(&mut frame, 2); // ← wraps back round to page 0
+        assert_eq!(row(&frame, 0), "a               ");
+        assert_eq!(row(&frame, 1), "b               ");
+    }
+
+    #[test]
+    fn from_codes_keeps_trailing_up_arrow() { // ← "Ward 3 " then CGR_UP, padded out the way const_dat does it
+        let mut desc = [SPACE; 32];
+        desc[..6].copy_from_slice(b"Ward 3");
+        desc[7] = 0x00;
+
+        let layout = Layout::from_codes(&desc, 16, Align::Left);
+        assert_eq!(layout.lines(), 1);
+        assert_eq!(layout.line(0), b"Ward 3 \x00");
+    }
+}
@@ -1,28 +1,38 @@
-use alloc::format;
-use alloc::rc::Rc;
 use alloc::vec::Vec;
-use core::cell::RefCell;
-use arduino_hal::hal::port::{Dynamic, PE0, PE1};
-use arduino_hal::port::mode::{Input, Output};
-use arduino_hal::port::Pin;
-use arduino_hal::prelude::{_embedded_hal_blocking_i2c_Read, _embedded_hal_blocking_i2c_Write, _embedded_hal_blocking_i2c_WriteRead, _unwrap_infallible_UnwrapInfallible};
-use arduino_hal::{I2c, Usart};
-use avr_device::atmega2560::USART0;
+use core::fmt;
 use core::ops::RangeBounds;
-use embedded_hal::digital::{OutputPin, PinState};
-use fchashmap::FcHashMap;
-use ufmt::uwriteln;
-use crate::bitops::{bits16, bits8};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{PinState, StatefulOutputPin};
+use embedded_hal::i2c::{ErrorKind, I2c};
+use embedded_hal::pwm::SetDutyCycle;
+use ufmt::{uDebug, uWrite, Formatter};
+use crate::bitops::bits8;
+use crate::cgram::{GlyphBank, GlyphError, CGRAM_SLOTS};
+use crate::cgrom;
+use crate::cgrom::CgRom;
+use crate::frame::Frame;
+use crate::anim::Marquee;
+use crate::backlight::{AutoDim, NoBacklight, FULL};
+use crate::backpack;
+use crate::backpack::{Expander, PinMap, MCP_GPIO, MCP_IODIR};
+use crate::geometry::Geometry;
+use crate::layout::{Align, Layout};
+use crate::widget;
+use crate::bignum;
+use crate::widget::{Bar, Gauge};
+use crate::{log, trace};
+use crate::translit;
+use crate::translit::Codes;
+use crate::gsearch::{CGR_DOWN, CGR_DOWNLEFT, CGR_DOWNRIGHT, CGR_UP, CGR_UPLEFT, CGR_UPRIGHT};
 // Adapted from https://www.waveshare.com/datasheet/LCD_en_PDF/LCD1602.pdf, https://cdn.sparkfun.com/assets/9/5/f/7/b/HD44780.pdf
 
-const I2C_RS: u8 = 0;
-const I2C_RW: u8 = 1;
-const I2C_E: u8 = 2;
-const I2C_BL: u8 = 3;
-const I2C_D0: u8 = 4;
-const I2C_D1: u8 = 5;
-const I2C_D2: u8 = 6;
-const I2C_D3: u8 = 7;
+
+const BUSY_POLL_US: u32 = 50;
+const BUSY_TIMEOUT_US: u32 = 10_000; // ← ~6x the slowest instruction (clear, 1.52 ms)
+
+const BLANK: u8 = 0b0010_0000;
+
+const AMARQUE_TICK: u32 = 10; // ← ms between amarque's ticks; well under any sane scroll step
 
 const CGRAM_UP: [[u8; 5]; 8] = {
     [
@@ -103,6 +113,60 @@ const CGRAM_DOWN_RIGHT: [[u8; 5]; 8] = {
     ]
 };
 
+// CGRAM slot order mirrors the CGR_* codes handed out by gsearch::ext_dm (slot n ⇔ DDRAM code n).
+const CGRAM_ARROWS: [[u8; 8]; 7] = [
+    cgpack(CGRAM_UP),         // CGR_UP
+    cgpack(CGRAM_DOWN),       // CGR_DOWN
+    cgpack(CGRAM_UP_LEFT),    // CGR_UPLEFT
+    cgpack(CGRAM_DOWN_RIGHT), // CGR_DOWNRIGHT
+    cgpack(CGRAM_UP_RIGHT),   // CGR_UPRIGHT
+    cgpack(CGRAM_DOWN_LEFT),  // CGR_DOWNLEFT
+    [0u8; 8]                  // ← free
+];
+
+// Which char each arrow glyph answers to once it's in the GlyphBank.
+pub(crate) const CGR_CHARS: [(u8, char); 6] = [
+    (CGR_UP, '↑'),
+    (CGR_DOWN, '↓'),
+    (CGR_UPLEFT, '↖'),
+    (CGR_DOWNRIGHT, '↘'),
+    (CGR_UPRIGHT, '↗'),
+    (CGR_DOWNLEFT, '↙')
+];
+
+pub fn cgr_char(code: u8) -> Option<char> { // ← CGR_* code (e.g. from ext_dm) → arrow char, so it survives CGRAM eviction
+    CGR_CHARS.iter().find(|(cgr, _)| *cgr == code).map(|(_, c)| *c)
+}
+
+pub(crate) fn stock_bank() -> GlyphBank { // ← arrows + bar/gauge blocks + big digit segments; only the arrows start out resident
+    let mut bank = GlyphBank::new();
+
+    for (cgr, c) in CGR_CHARS {
+        bank.register(c, CGRAM_ARROWS[cgr as usize]);
+    }
+
+    widget::register(&mut bank);
+    bignum::register(&mut bank);
+    bank
+}
+
+const fn cgpack(sym: [[u8; 5]; 8]) -> [u8; 8] { // ← 5x8 bitmap → 8 CGRAM rows (leftmost column = bit 4)
+    let mut rows = [0u8; 8];
+    let mut r = 0;
+
+    while r < 8 {
+        let mut c = 0;
+        while c < 5 {
+            rows[r] = (rows[r] << 1) | (sym[r][c] & 0x1);
+            c += 1;
+        }
+        r += 1;
+    }
+
+    rows
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum MarqueStyle {
     SoloHighL,
     SoloHighR,
@@ -114,1061 +178,1556 @@ pub enum MarqueStyle {
     GearRight
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LcdError {
+    Nack,             // ← nobody acknowledged (wrong address, backpack unplugged)
+    ArbitrationLoss,  // ← another master grabbed the bus mid-transfer
+    Bus,              // ← any other I2C fault (overrun, HAL-specific)
+    Pin,              // ← a parallel GPIO (or the backlight PWM) refused to change state
+    BusyTimeout,      // ← BF still set after BUSY_TIMEOUT_US; controller wedged or RW not wired
+    Unmappable(char), // ← not on the ROM, no custom glyph, no respelling; the replacement glyph was drawn instead
+    OutOfRange,       // ← DDRAM/CGRAM address, row or slot the controller (or the panel) doesn't have
+    NoReadback,       // ← RW isn't wired (or the expander can't turn the bus around), so the panel can't be read
+    NotFound          // ← discover() scanned every expander address and nobody answered
+}
+
+impl LcdError {
+    pub(crate) fn from_i2c<E: embedded_hal::i2c::Error>(e: E) -> Self {
+        match e.kind() {
+            ErrorKind::NoAcknowledge(_) => LcdError::Nack,
+            ErrorKind::ArbitrationLoss => LcdError::ArbitrationLoss,
+            _ => LcdError::Bus
+        }
+    }
+
+    fn from_pin<E>(_: E) -> Self {
+        LcdError::Pin
+    }
+}
+
+impl uDebug for LcdError {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        match self {
+            LcdError::Nack => f.write_str("Nack"),
+            LcdError::ArbitrationLoss => f.write_str("ArbitrationLoss"),
+            LcdError::Bus => f.write_str("Bus"),
+            LcdError::Pin => f.write_str("Pin"),
+            LcdError::BusyTimeout => f.write_str("BusyTimeout"),
+            LcdError::Unmappable(c) => {
+                f.write_str("Unmappable(")?;
+                f.write_str(c.encode_utf8(&mut [0u8; 4]))?;
+                f.write_str(")")
+            }
+            LcdError::OutOfRange => f.write_str("OutOfRange"),
+            LcdError::NoReadback => f.write_str("NoReadback"),
+            LcdError::NotFound => f.write_str("NotFound")
+        }
+    }
+}
+
+pub(crate) fn exec_us(reg: &u16) -> u32 { // ← datasheet Table 6 execution times (fosc = 270 kHz)
+    match reg & 0b11_1111_1110 {
+        0b00_0000_0000 | 0b00_0000_0010 => 1520, // ← clear / return home
+        _ if reg & 0b10_0000_0000 != 0 => 41,    // ← data read/write, +tADD for the AC bump
+        _ => 37
+    }
+}
+
+pub(crate) fn ddram(addr: u8) -> Result<u16, LcdError> { // ← 2-line mode only has 0x00-0x27 and 0x40-0x67
+    match addr {
+        0x00..=0x27 | 0x40..=0x67 => Ok(addr as u16),
+        _ => Err(LcdError::OutOfRange)
+    }
+}
+
+fn soft(res: Result<(), LcdError>, unmapped: &mut Option<char>) -> Result<(), LcdError> { // ← keep going past an Unmappable, but remember the first one
+    match res {
+        Err(LcdError::Unmappable(c)) => {
+            unmapped.get_or_insert(c);
+            Ok(())
+        }
+        res => res
+    }
+}
+
+pub(crate) fn cgram(addr: u8) -> Result<u16, LcdError> {
+    match addr {
+        0x00..=0x3F => Ok(addr as u16),
+        _ => Err(LcdError::OutOfRange)
+    }
+}
+
+struct Shadow { // ← AC bookkeeping replayed from the instruction stream
+    ac: u8,
+    id: bool
+}
+
+impl Shadow {
+    fn new() -> Self {
+        Self { ac: 0, id: true }
+    }
+
+    fn track(&mut self, reg: &u16) {
+        let data = (reg & 0xFF) as u8;
+
+        match reg >> 8 {
+            0b10 | 0b11 => self.step(self.id), // ← data write/read
+            0b00 => match data.leading_zeros() {
+                0 => self.ac = data & 0x7F,
+                1 => self.ac = data & 0x3F,
+                3 if data & 0b1000 == 0 => self.step(data & 0b0100 != 0), // ← cursor (not display) shift
+                5 => self.id = data & 0b10 != 0,
+                6 => self.ac = 0,
+                7 => { self.ac = 0; self.id = true; }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    fn step(&mut self, up: bool) { // ← DDRAM's 0x27 ↔ 0x40 seam; CGRAM's own wrap is ignored, nobody asks for AC mid-upload
+        self.ac = match (self.ac, up) {
+            (0x27, true) => 0x40,
+            (0x67, true) => 0x00,
+            (0x00, false) => 0x67,
+            (0x40, false) => 0x27,
+            (ac, true) => ac + 1,
+            (ac, false) => ac - 1
+        };
+    }
+}
+
 pub trait HD44780Kernel {
-    fn bus(&mut self);
-    fn enp(&mut self);
-    fn enp_then_bus(&mut self);
-    fn cmd(&mut self, reg: &u16);
-    fn cmb(&mut self, reg: &u16);
-    fn rdb(&mut self) -> bool;
-    fn clr(&mut self);
-    fn ret(&mut self);
-    fn ems(&mut self, id: bool, s: bool);
-    fn dsw(&mut self, d: bool, c: bool, b: bool);
-    fn cds(&mut self, sc: bool, rl: bool);
-    fn fns(&mut self, dl: bool, n: bool, f: bool);
-    fn cgs(&mut self, addr: u8);
-    fn dds(&mut self, addr: u8);
-    fn dtw(&mut self, data: u8);
-    fn dtr(&mut self) -> u8;
+    fn bus(&mut self, reg: &u16) -> Result<(), LcdError> { // ← wait until reg (just sent) has finished executing
+        if !self.polls() {
+            self.dlu(exec_us(reg));
+            return Ok(());
+        }
+
+        for _ in 0..(BUSY_TIMEOUT_US / BUSY_POLL_US) {
+            if !self.rdb()? {
+                return Ok(());
+            }
+            self.dlu(BUSY_POLL_US);
+        }
+
+        Err(LcdError::BusyTimeout)
+    }
+
+    fn polls(&self) -> bool; // ← can BF actually be read back on this wiring?
+    fn enp(&mut self) -> Result<(), LcdError>;
+    fn cmd(&mut self, reg: &u16) -> Result<(), LcdError>;
+    fn cmb(&mut self, reg: &u16) -> Result<(), LcdError>;
+    fn rdb(&mut self) -> Result<bool, LcdError>;
+    fn clr(&mut self) -> Result<(), LcdError>;
+    fn ret(&mut self) -> Result<(), LcdError>;
+    fn ems(&mut self, id: bool, s: bool) -> Result<(), LcdError>;
+    fn dsw(&mut self, d: bool, c: bool, b: bool) -> Result<(), LcdError>;
+    fn cds(&mut self, sc: bool, rl: bool) -> Result<(), LcdError>;
+    fn fns(&mut self, dl: bool, n: bool, f: bool) -> Result<(), LcdError>;
+    fn cgs(&mut self, addr: u8) -> Result<(), LcdError>;
+    fn dds(&mut self, addr: u8) -> Result<(), LcdError>;
+    fn dtw(&mut self, data: u8) -> Result<(), LcdError>;
+    fn dtr(&mut self) -> Result<u8, LcdError>;
+    fn boot(&mut self) -> Result<(), LcdError>; // ← power-on handshake up to (and including) function set; bus width is backend business
+    fn dlm(&mut self, ms: u32);
+    fn dlu(&mut self, us: u32);
+    fn rac(&mut self) -> Result<u8, LcdError>; // ← read address counter (BF masked off)
 }
 
 pub trait HD44780Util {
-    fn cgload(&mut self, data: [[u8;8];7]);
-    fn init(&mut self);
-    fn affix(&mut self, row: u8, str: &str);
-    fn marque(&mut self, iters: u8, graceful: bool);
-    fn demo(&mut self, row0: &str, row1: &str, iters: u8, graceful: bool);
+    fn cgload(&mut self, data: [[u8;8];7]) -> Result<(), LcdError>;
+    fn cgput(&mut self, slot: u8, rows: [u8; 8]) -> Result<(), LcdError>;
+    fn init(&mut self) -> Result<(), LcdError>;
+    fn affix(&mut self, row: u8, str: &str) -> Result<(), LcdError>;
+    fn marque(&mut self, iters: u8, graceful: bool) -> Result<(), LcdError>;
+    fn demo(&mut self, row0: &str, row1: &str, iters: u8, graceful: bool) -> Result<(), LcdError>;
+    fn paint(&mut self, frame: &mut Frame, str: &str) -> Result<(), LcdError>;
+    fn flush(&mut self, frame: &mut Frame) -> Result<(), LcdError>;
+    fn layout(&mut self, str: &str, align: Align) -> Result<Layout, LcdError>;
+    fn leaf(&mut self, layout: &Layout, frame: &mut Frame, ms: u32, rounds: u8) -> Result<(), LcdError>;
+    fn encode_str(&mut self, str: &str) -> Result<Vec<u8>, LcdError>;
+    fn amarque(&mut self, marquee: &mut Marquee, frame: &mut Frame, ms: u32) -> Result<(), LcdError>;
+    fn bar(&mut self, frame: &mut Frame, bar: &Bar, value: u16, max: u16) -> Result<(), LcdError>;
+    fn gauge(&mut self, frame: &mut Frame, gauge: &Gauge, value: u16, max: u16) -> Result<(), LcdError>;
+    fn big(&mut self, frame: &mut Frame, row: u8, col: u8, str: &str) -> Result<(), LcdError>;
+    fn timer(&mut self, msg: &str, duration: u16) -> Result<(), LcdError>;
+    fn bsd(&mut self, row: u8, dist: i8, cyclic: bool) -> Result<(), LcdError>;
+    fn bso(&mut self, row: u8, fw: bool) -> Result<(), LcdError>;
+    fn ls(&mut self, row: u8, rightward: bool, ms: u32, laps: u8) -> Result<(), LcdError>;
 }
 
-pub trait Lcd1602 {
-    fn disp_char(&mut self, c: char);
-    fn disp_sym(&mut self, sym: u8);
-    fn disp_str(&mut self, str: &str);
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Miss { // ← soft failures worth a log line; something stand-in is on screen regardless
+    CgFull(char, u8), // ← custom glyph with no free slot (u8 = custom glyphs the screen wanted)
+    NoMap(char),      // ← not even transliteration could place it
+    Crowded(u8)       // ← one string wants more custom glyphs than CGRAM holds
+}
+
+pub trait Lcd1602: HD44780Kernel { // ← char → screen; every backend shares it and only hands over its state
+    fn state(&mut self) -> (&mut GlyphBank, CgRom, u8); // ← (custom glyphs, character ROM, replacement code)
+    fn set_rom(&mut self, rom: CgRom); // ← which character ROM the panel shipped with (A00 by default)
+    fn set_replacement(&mut self, sym: u8); // ← CGROM code drawn when even transliteration gives up
+    fn geometry(&self) -> Geometry;
+    fn set_geometry(&mut self, geometry: Geometry); // ← 16x2 by default
+
+    fn miss(&mut self, _: Miss) {} // ← backends with a log sink say something here
+
+    fn glyphs(&mut self) -> &mut GlyphBank {
+        self.state().0
+    }
+
+    fn disp_char(&mut self, c: char) -> Result<(), LcdError> { // ← Unmappable only after the replacement is on screen
+        let codes = self.encode(c)?;
+        let replaced = codes.replaced;
+
+        for sym in codes {
+            self.disp_sym(sym)?;
+        }
+
+        if replaced { Err(LcdError::Unmappable(c)) } else { Ok(()) }
+    }
+
+    fn encode(&mut self, c: char) -> Result<Codes, LcdError> { // ← char → CGROM code(s), uploading its custom glyph first if need be
+        let (glyphs, rom, replacement) = self.state();
+        if let Some(sym) = cgrom::lookup(c, rom) {
+            return Ok(Codes::of(sym));
+        }
+
+        match glyphs.resolve(c) {
+            Ok((code, load)) => {
+                if let Some(rows) = load {
+                    self.cgput(code, rows)?;
+                }
+                Ok(Codes::of(code))
+            }
+            Err(GlyphError::Overflow(n)) => {
+                self.miss(Miss::CgFull(c, n));
+                let mut codes = Codes::of(replacement);
+                codes.replaced = true;
+                Ok(codes)
+            }
+            Err(GlyphError::Unregistered(_)) => { // ← not on the ROM, not a custom glyph: respell it
+                let codes = translit::codes(c, rom, replacement);
+                if codes.replaced {
+                    self.miss(Miss::NoMap(c));
+                }
+                Ok(codes)
+            }
+        }
+    }
+
+    fn disp_sym(&mut self, sym: u8) -> Result<(), LcdError> {
+        let ac = self.rac().ok(); // ← an empty Mirror has nobody to ask; nothing lands either
+        self.dtw(sym)?;
+        if let Some(ac) = ac {
+            self.glyphs().wrote(ac, sym); // ← lets a glyph overwritten in place give its slot back
+        }
+        Ok(())
+    }
+
+    fn disp_str(&mut self, str: &str) -> Result<(), LcdError> { // ← draws the whole string before reporting the first Unmappable
+        match self.glyphs().plan(str) { // ← upload everything up front so one glyph can't evict another mid-string
+            Ok(loads) => {
+                for (slot, rows) in loads {
+                    self.cgput(slot, rows)?;
+                }
+            }
+            Err(GlyphError::Overflow(n)) => self.miss(Miss::Crowded(n)),
+            Err(_) => {}
+        }
+
+        let mut unmapped = None;
+        for c in str.chars() {
+            match self.disp_char(c) {
+                Err(LcdError::Unmappable(c)) => { unmapped.get_or_insert(c); }
+                res => res?
+            }
+        }
+
+        unmapped.map_or(Ok(()), |c| Err(LcdError::Unmappable(c)))
+    }
+}
+
+fn reseat<T: HD44780Util + Lcd1602 + ?Sized>(lcd: &mut T, frame: &mut Frame) -> Result<Option<char>, LcdError> { // ← slots rewritten under the frame's feet (e.g. by a Mirror) get their glyph back, wherever it fits
+    let used = frame.slots();
+    lcd.glyphs().pin(used); // ← what the frame holds must survive whatever comes next, flushed or not
+
+    let mut lost = None;
+    let mut moves = Vec::new();
+    for slot in (0..CGRAM_SLOTS as u8).filter(|slot| used & (1 << slot) != 0) {
+        let Some(c) = frame.meant(slot) else { continue };
+        if lcd.glyphs().resident(slot) == Some(c) {
+            continue;
+        }
+
+        match lcd.glyphs().resolve(c) {
+            Ok((code, load)) => {
+                if let Some(rows) = load {
+                    lcd.cgput(code, rows)?;
+                }
+                moves.push((slot, code));
+            }
+            Err(_) => { // ← every other slot is on screen too
+                lost.get_or_insert(c);
+                moves.push((slot, BLANK));
+            }
+        }
+    }
+
+    frame.remap(&moves);
+    frame.note(core::array::from_fn(|slot| lcd.glyphs().resident(slot as u8)));
+    Ok(lost)
+}
+
+fn report<L: uWrite>(log: &mut L, miss: Miss) {
+    match miss {
+        Miss::CgFull(c, n) => log!(*log, "CGFULL => {} ({} glyphs)", c, n),
+        Miss::NoMap(c) => log!(*log, "NOMAP => {}", c),
+        Miss::Crowded(n) => log!(*log, "CGFULL => {} glyphs on one screen", n)
+    }
+}
+
+pub trait Backlight {
+    fn blw(&mut self, level: u8) -> Result<(), LcdError>; // ← drive the LED right now, bypassing the dimmer's bookkeeping
+    fn dimmer(&mut self) -> &mut AutoDim;
+
+    fn set_backlight(&mut self, level: u8) -> Result<(), LcdError> { // ← 0 = off, 255 = full
+        self.dimmer().set_bright(level);
+        let level = self.dimmer().level();
+        self.blw(level)
+    }
+
+    fn bl(&mut self, on: bool) -> Result<(), LcdError> {
+        self.set_backlight(if on { FULL } else { 0 })
+    }
+
+    fn set_auto_dim(&mut self, after_ms: u32, dim: u8) { // ← after_ms = 0 turns it off again
+        self.dimmer().set(after_ms, dim);
+    }
+
+    fn wake(&mut self, now_ms: u32) -> Result<(), LcdError> { // ← someone's here; back to full brightness
+        match self.dimmer().wake(now_ms) {
+            Some(level) => self.blw(level),
+            None => Ok(())
+        }
+    }
+
+    fn idle(&mut self, now_ms: u32) -> Result<(), LcdError> { // ← call from the main loop; dims once nobody has woken it for a while
+        match self.dimmer().idle(now_ms) {
+            Some(level) => self.blw(level),
+            None => Ok(())
+        }
+    }
 }
 
 trait I2CBlOps { // ← blops stands for "byte-level ops"
-    fn iiw(&mut self, data: &u8);
-    fn iir(&mut self) -> u8;
-    fn iirw(&mut self, data: &u8) -> u8;
+    fn iiw(&mut self, data: &u8) -> Result<(), LcdError>;
+    fn iir(&mut self) -> Result<u8, LcdError>;
 }
 
 trait Pinnable { // ← pin-level operations; requires struct to have buffer attribute
     fn pin(&mut self, i: u8, state: bool);
-    fn fin(&mut self) -> u8;
-    fn gin(&mut self, i: u8, state: bool);
-    fn bin(&mut self, bitmask: u8, state: u8);
+    fn fin(&mut self) -> Result<u8, LcdError>;
+    fn gin(&mut self, i: u8, state: bool) -> Result<(), LcdError>;
+}
+
+pub enum DataBus<P> { // ← how many DB lines are actually wired
+    Octet([P; 8]), // ← DB0-DB7, DL=8D
+    Nibble([P; 4]) // ← DB4-DB7 only, DL=4D (DB0-DB3 left unconnected)
 }
 
-pub struct ParallelLcd1602 {
-    rs: Pin<Output>,
-    rw: Pin<Output>,
-    en: Pin<Output>,
-    db: [Pin<Output>; 8],// ← NOTE... little endian (0-7)
-    serial: Usart<USART0, Pin<Input, PE0>, Pin<Output, PE1>>,
-    mapper: FcHashMap<char, u8, 256>,
-    anchor: u8, // TODO: account for EMS S = 0.
-    overcast: u8
+pub struct ParallelLcd1602<P, D, L, B = NoBacklight> { // ← P = any (stateful) output pin, D = delay source, L = log sink (see log.rs), B = backlight PWM
+    rs: P,
+    rw: P,
+    en: P,
+    db: DataBus<P>,// ← NOTE... little endian (0-7 or 4-7)
+    bl: B,
+    dimmer: AutoDim,
+    delay: D,
+    log: L,
+    glyphs: GlyphBank,
+    rom: CgRom,
+    replacement: u8,
+    geometry: Geometry,
+    shadow: Shadow // ← DB pins never turn around, so AC is only ever known from what we sent
 }
 
-pub struct I2CLcd1602 {
-    serial: Usart<USART0, Pin<Input, PE0>, Pin<Output, PE1>>,
-    i2c: I2c,
-    mapper: FcHashMap<char, u8, 256>,
+pub struct I2CLcd1602<I, D, L> { // ← I = any I2C bus, D = delay source, L = log sink (see log.rs)
+    log: L,
+    i2c: I,
+    delay: D,
+    glyphs: GlyphBank,
+    rom: CgRom,
+    replacement: u8,
+    geometry: Geometry,
     target: u8,
     buf: u8,
-    anchor: u8,
-    overcast: u8
+    dimmer: AutoDim, // ← the backpack's BL transistor is on/off only; any level above 0 is "on"
+    map: PinMap,
+    poll: bool, // ← poll BF instead of sleeping; needs RW wired (it is on most backpacks, not Adafruit's)
+    shadow: Shadow // ← AC as far as we know it, for backpacks that can't be asked
 }
 
-impl HD44780Kernel for ParallelLcd1602 {
-    fn bus(&mut self) { // ← imagine a bus... wait at the bus stop... framerules... :p
-        while self.rdb() {}
+impl<P: StatefulOutputPin, D: DelayNs, L: uWrite, B: SetDutyCycle> HD44780Kernel for ParallelLcd1602<P, D, L, B> {
+    fn polls(&self) -> bool { // ← imagine a bus... wait at the bus stop... framerules... :p
+        false // ← DB pins are output-only (StatefulOutputPin), so BF never makes it back; datasheet timings it is
     }
 
-    fn enp(&mut self) { // ← enable (E) pulse
-        self.en.set_high();
-        arduino_hal::delay_us(1);
-        self.en.set_low();
-        arduino_hal::delay_us(1);
-        ufmt::uwriteln!(&mut self.serial, "ENP OK");
+    fn enp(&mut self) -> Result<(), LcdError> { // ← enable (E) pulse
+        self.en.set_high().map_err(LcdError::from_pin)?;
+        self.delay.delay_us(1);
+        self.en.set_low().map_err(LcdError::from_pin)?;
+        self.delay.delay_us(1);
+        trace!(self.log, "ENP OK");
+        Ok(())
     }
 
-    fn enp_then_bus(&mut self) {
-        self.enp();
-        self.bus();
+    fn cmd(&mut self, reg: &u16) -> Result<(), LcdError> { // ← the "skip pleasantries and go for it" option
+        self.cmb(reg)?;
+        self.bus(reg)
     }
 
-    fn cmd(&mut self, reg: &u16) { // ← the "skip pleasantries and go for it" option
-        self.cmb(reg);
-        self.bus();
-    }
+    fn cmb(&mut self, reg: &u16) -> Result<(), LcdError> { // cmd with no busing
+        trace!(self.log, "CMD: {} {} / {:?}", (reg >> 9) & 0b1u16, (reg >> 8) & 0b1u16, bits8((reg & 0xFF) as u8));
+
+        let byte = (reg & 0b00_1111_1111) as u8;
+        self.register(if self.db.is_nibble() { byte >> 4 } else { byte })?;
+        self.rw.set_state(PinState::from((reg & 0b01_0000_0000) != 0)).map_err(LcdError::from_pin)?;
+        self.rs.set_state(PinState::from((reg & 0b10_0000_0000) != 0)).map_err(LcdError::from_pin)?;
+        self.enp()?;
 
-    fn cmb(&mut self, reg: &u16) { // cmd with no busing
-        ufmt::uwriteln!(&mut self.serial, "CMD: {} {} / {:?}", (reg >> 9) & 0b1u16, (reg >> 8) & 0b1u16, bits8((reg & 0xFF) as u8));
+        if self.db.is_nibble() { // ← 4-bit bus: upper nibble first, then lower (datasheet Figure 9)
+            self.register(byte & 0x0F)?;
+            self.enp()?;
+        }
 
-        self.register((reg & 0b00_1111_1111) as u8);
-        self.rw.set_state(PinState::from((reg & 0b01_0000_0000) != 0)).expect("Could not set register pin state");
-        self.rs.set_state(PinState::from((reg & 0b10_0000_0000) != 0)).expect("Could not set register pin state");
-        self.enp();
+        #[cfg(feature = "trace")]
         self.check();
+        self.shadow.track(reg);
+        Ok(())
     }
 
-    fn rdb(&mut self) -> bool { // ← Read B(usy) flag
-        self.rs.set_low();
-        self.rw.set_high();
-        self.enp();
-
-        self.en.is_set_high()
+    fn rdb(&mut self) -> Result<bool, LcdError> { // ← Read B(usy) flag... which would mean RW high while we still drive DB. Not with output-only pins.
+        Err(LcdError::NoReadback)
     }
 
-    fn clr(&mut self) { // ← screen clear
-        self.cmd(&0b00_0000_0001);
+    fn clr(&mut self) -> Result<(), LcdError> { // ← screen clear
+        self.cmd(&0b00_0000_0001)?;
+        self.glyphs.release();
+        Ok(())
     }
 
-    fn ret(&mut self) { // ← cursor return
+    fn ret(&mut self) -> Result<(), LcdError> { // ← cursor return
         let bits = &(0b00_0000_0010 | (self.dbx(0..=0) as u16));
-        self.cmd(bits);
+        self.cmd(bits)
     }
 
-    fn ems(&mut self, id: bool, s: bool) { // ← entry mode set
-        self.cmd(&(0b00_0000_0100 | ((id as u16) << 1) | (s as u16)));
+    fn ems(&mut self, id: bool, s: bool) -> Result<(), LcdError> { // ← entry mode set
+        self.cmd(&(0b00_0000_0100 | ((id as u16) << 1) | (s as u16)))
     }
 
-    fn dsw(&mut self, d: bool, c: bool, b: bool) { // ← display switch
-        self.cmd(&(0b00_0000_1000 | ((d as u16) << 2) | ((c as u16) << 1) | (b as u16)));
+    fn dsw(&mut self, d: bool, c: bool, b: bool) -> Result<(), LcdError> { // ← display switch
+        self.cmd(&(0b00_0000_1000 | ((d as u16) << 2) | ((c as u16) << 1) | (b as u16)))
     }
 
-    fn cds(&mut self, sc: bool, rl: bool) { // ← cursor/display shift
+    fn cds(&mut self, sc: bool, rl: bool) -> Result<(), LcdError> { // ← cursor/display shift
         let bits = &(0b00_0001_0000 | ((sc as u16) << 3) | ((rl as u8) << 2 | self.dbx(0..=1)) as u16);
-        self.cmd(bits);
+        self.cmd(bits)
     }
 
-    fn fns(&mut self, dl: bool, n: bool, f: bool) { // ← function set
+    fn fns(&mut self, dl: bool, n: bool, f: bool) -> Result<(), LcdError> { // ← function set
         let bits = &(0b00_0010_0000 | ((dl as u16) << 4) | ((n as u16) << 3) | ((f as u8) << 2 | self.dbx(0..=1)) as u16);
-        self.cmd(bits);
+        self.cmd(bits)
     }
 
-    fn cgs(&mut self, addr: u8) { // ← CGRAM set address
-        self.cmd(&(0b00_0100_0000 | (addr as u16) & 0b00_0011_1111));
+    fn cgs(&mut self, addr: u8) -> Result<(), LcdError> { // ← CGRAM set address
+        self.cmd(&(0b00_0100_0000 | cgram(addr)?))
     }
 
-    fn dds(&mut self, addr: u8) { // ← DDRAM set address
-        self.cmb(&(0b00_1000_0000 | (addr as u16) & 0b00_0111_1111));
-        arduino_hal::delay_us(70);
+    fn dds(&mut self, addr: u8) -> Result<(), LcdError> { // ← DDRAM set address
+        self.cmd(&(0b00_1000_0000 | ddram(addr)?))
     }
 
-    fn dtw(&mut self, data: u8) { // ← Data write (cgs/dds 1st!)
-        self.cmb(&(0b10_0000_0000 | (data as u16))); // Froze on busing, so manual delay override.
-        arduino_hal::delay_us(70);
+    fn dtw(&mut self, data: u8) -> Result<(), LcdError> { // ← Data write (cgs/dds 1st!)
+        self.cmd(&(0b10_0000_0000 | (data as u16))) // Froze on busing back when rdb read E; polls() = false sidesteps that.
     }
 
-    fn dtr(&mut self) -> u8 { // ← Data read (cgs/dds 1st!)
-        self.rs.set_high();
-        self.rw.set_high();
-        self.enp();
+    fn dtr(&mut self) -> Result<u8, LcdError> { // ← Data read: same story as rdb; dbx() would only hand back our own output latches
+        Err(LcdError::NoReadback)
+    }
 
-        self.dbx(0..8)
+    fn boot(&mut self) -> Result<(), LcdError> {
+        // See Figure 23 (8-bit) / Figure 24 (4-bit) of Hitachi HD44780U datasheet; manual initialisation
+        self.delay.delay_ms(150);
+
+        if self.db.is_nibble() {
+            self.nib(0b0011)?; // ← still "DL=8D" as far as the controller knows; only DB7-DB4 are latched
+            self.delay.delay_ms(10);
+            self.nib(0b0011)?;
+            self.delay.delay_us(150);
+            self.nib(0b0011)?;
+            self.delay.delay_us(150);
+            self.nib(0b0010)?; // ← switch to 4-bit; every instruction from here on is split in two
+            self.delay.delay_us(150);
+            self.cmd(&0b00_0010_1000)?; // DL=4D, N=2R, F=5x7
+        } else {
+            self.cmb(&0b00_0011_0000)?;
+            self.delay.delay_ms(10);
+            self.cmb(&0b00_0011_0000)?;
+            self.delay.delay_us(150);
+            self.cmb(&0b00_0011_0000)?;
+            self.delay.delay_us(150);
+            self.cmd(&0b00_0011_1000)?; // DL=8D, N=2R, F=5x7
+        }
+
+        log!(self.log, "\n\nInitialised.\n\n");
+        Ok(())
+    }
+
+    fn dlm(&mut self, ms: u32) {
+        self.delay.delay_ms(ms);
+    }
+
+    fn dlu(&mut self, us: u32) {
+        self.delay.delay_us(us);
+    }
+
+    fn rac(&mut self) -> Result<u8, LcdError> {
+        Ok(self.shadow.ac) // ← enough for cgput to find its way back
     }
 }
 
-impl HD44780Kernel for I2CLcd1602 { // ~~← [[ RS RW E D4 D5 D6 D7 _ ]]~~ [[ D7/3 D6/2 D5/1 D4/0 BL EN RW RS ]]
-    fn bus(&mut self) {
-        while self.rdb() {}
+impl<I: I2c, D: DelayNs, L: uWrite> HD44780Kernel for I2CLcd1602<I, D, L> { // ← which expander pin is which lives in self.map (see backpack.rs)
+    fn polls(&self) -> bool {
+        self.poll
     }
 
-    fn enp(&mut self) {
-        self.gin(I2C_E, true);
-        arduino_hal::delay_us(1);
-        self.gin(I2C_E, false);
-        arduino_hal::delay_us(1);
-        ufmt::uwriteln!(&mut self.serial, "ENP OK");
+    fn enp(&mut self) -> Result<(), LcdError> {
+        self.gin(self.map.e, true)?;
+        self.delay.delay_us(1);
+        self.gin(self.map.e, false)?;
+        self.delay.delay_us(1);
+        trace!(self.log, "ENP OK");
+        Ok(())
     }
 
-    fn enp_then_bus(&mut self) {
-        self.enp();
-        self.bus();
+    fn cmd(&mut self, reg: &u16) -> Result<(), LcdError> {
+        self.cmb(reg)?;
+        self.bus(reg)
     }
 
-    fn cmd(&mut self, reg: &u16) {
-        self.cmb(reg);
-        self.bus();
+    fn cmb(&mut self, reg: &u16) -> Result<(), LcdError> { // ← only DB7-DB4 reach the backpack, so every instruction is two E-strobed nibbles
+        let byte = (reg & 0b00_1111_1111) as u8;
+        self.pin(self.map.rs, (reg & 0b10_0000_0000) != 0);
+        self.rw((reg & 0b01_0000_0000) != 0);
+
+        self.nbw(byte >> 4)?;
+        trace!(self.log, "U: {:?}", bits8(self.buf));
+        self.nbw(byte & 0x0F)?;
+        trace!(self.log, "L: {:?}\n\n", bits8(self.buf));
+        self.shadow.track(reg);
+        Ok(())
     }
 
-    fn cmb(&mut self, reg: &u16) {
-        let append = ((reg & 0b11_0000_0000) >> 4) as u8;  // ← RS, RW
-        self.iiw(&(append + ((reg & 0b1111_0000) >> 4) as u8));
-        uwriteln!(self.serial, "U: {:?}", bits8(append + ((reg & 0b1111_0000) >> 4) as u8));
-        self.bus();
-        self.iiw(&(append + (reg & 0b0000_1111) as u8));
-        uwriteln!(self.serial, "L: {:?}\n\n", bits8(append + (reg & 0b0000_1111) as u8));
+    fn rdb(&mut self) -> Result<bool, LcdError> {
+        Ok(self.rdw(false)? & 0b1000_0000 != 0) // BF set high
     }
 
-    fn rdb(&mut self) -> bool {
-        self.bin(0b1100_0000, 0b0100_0000);
-        self.fin();
-        self.enp();
-        self.iir() & 0b0010_0000 != 0 // BF set high
+    fn clr(&mut self) -> Result<(), LcdError> {
+        self.cmd(&0b00_0000_0001)?;
+        self.glyphs.release();
+        Ok(())
     }
 
-    fn clr(&mut self) {
-        self.cmb(&0b00_0000_0001);
+    fn ret(&mut self) -> Result<(), LcdError> {
+        self.cmd(&0b00_0000_0010)
     }
 
-    fn ret(&mut self) {
-        self.cmbm(&0b11_1111_1110, &0b00_0000_0010);
+    fn ems(&mut self, id: bool, s: bool) -> Result<(), LcdError> {
+        self.cmd(&(0b00_0000_0100 | ((id as u16) << 1) | (s as u16)))
     }
 
-    fn ems(&mut self, id: bool, s: bool) {
-        self.cmb(&(0b00_0000_0100 | ((id as u16) << 1) | (s as u16)));
+    fn dsw(&mut self, d: bool, c: bool, b: bool) -> Result<(), LcdError> {
+        self.cmd(&(0b00_0000_1000 | ((d as u16) << 2) | ((c as u16) << 1) | (b as u16)))
     }
 
-    fn dsw(&mut self, d: bool, c: bool, b: bool) {
-        self.cmb(&(0b00_0000_1000 | ((d as u16) << 2) | ((c as u16) << 1) | (b as u16)));
+    fn cds(&mut self, sc: bool, rl: bool) -> Result<(), LcdError> {
+        self.cmd(&(0b00_0001_0000 | ((sc as u16) << 3) | ((rl as u16) << 2)))
     }
 
-    fn cds(&mut self, sc: bool, rl: bool) {
-        self.cmbm(&0b11_1111_1100, &(0b00_0001_0000 | ((sc as u16) << 3) | ((rl as u16) << 2)));
+    fn fns(&mut self, dl: bool, n: bool, f: bool) -> Result<(), LcdError> {
+        self.cmd(&(0b00_0010_0000 | ((dl as u16) << 4) | ((n as u16) << 3) | ((f as u16) << 2)))
     }
 
-    fn fns(&mut self, dl: bool, n: bool, f: bool) {
-        self.cmbm(&0b11_1111_1100, &(0b00_0010_0000 | ((dl as u16) << 4) | ((n as u16) << 3) | ((f as u16) << 2)));
+    fn cgs(&mut self, addr: u8) -> Result<(), LcdError> {
+        self.cmd(&(0b00_0100_0000 | cgram(addr)?))
     }
 
-    fn cgs(&mut self, addr: u8) {
-        self.cmb(&(0b00_0100_0000 | (addr as u16) & 0b00_0011_1111));
+    fn dds(&mut self, addr: u8) -> Result<(), LcdError> {
+        self.cmd(&(0b00_1000_0000 | ddram(addr)?))
     }
 
-    fn dds(&mut self, addr: u8) {
-        self.cmb(&(0b00_1000_0000 | (addr as u16) & 0b00_0111_1111));
-        arduino_hal::delay_us(70);
+    fn dtw(&mut self, data: u8) -> Result<(), LcdError> {
+        self.cmd(&(0b10_0000_0000 | (data as u16)))
     }
 
-    fn dtw(&mut self, data: u8) {
-        self.cmd(&(0b10_0000_0000 | (data as u16))); // Froze on busing, so manual delay override.
-        arduino_hal::delay_us(70);
+    fn dtr(&mut self) -> Result<u8, LcdError> {
+        let data = self.rdw(true)?;
+        self.shadow.track(&0b11_0000_0000);
+        self.bus(&0b11_0000_0000)?; // ← AC still has to auto-increment after the read
+        Ok(data)
     }
 
-    fn dtr(&mut self) -> u8 {
-        self.cmbm(&0b11_0000_0000, &0b11_0000_0000);
-        self.enp();
-        self.iir()  // TODO how to read both nibbles?????f
+    // https://web.alfredstate.edu/faculty/weimandn/lcd/lcd_initialization/lcd_initialization_index.html
+    fn boot(&mut self) -> Result<(), LcdError> {
+        // See Figure 24 of Hitachi HD44780U datasheet; the backpack only wires DB7-DB4
+        self.delay.delay_ms(150);
+        if self.map.expander == Expander::Mcp23008 {
+            self.i2c.write(self.target, &[MCP_IODIR, 0x00]).map_err(LcdError::from_i2c)?; // ← every GP pin an output
+        }
+
+        self.pin(self.map.rs, false);
+        self.rw(false);
+        self.nbw(0b0011)?;
+        self.delay.delay_ms(10);
+        self.nbw(0b0011)?;
+        self.delay.delay_us(150);
+        self.nbw(0b0011)?;
+        self.delay.delay_us(150);
+        self.nbw(0b0010)?; // ← now in 4-bit mode, BF can be polled from here on
+        self.delay.delay_us(150);
+        self.cmd(&0b00_0010_1000)?; // DL=4D, N=2R, F=5x7
+
+        log!(self.log, "\n\nInitialised.\n\n");
+        Ok(())
+    }
+
+    fn dlm(&mut self, ms: u32) {
+        self.delay.delay_ms(ms);
+    }
+
+    fn dlu(&mut self, us: u32) {
+        self.delay.delay_us(us);
+    }
+
+    fn rac(&mut self) -> Result<u8, LcdError> {
+        Ok(self.shadow.ac) // ← exact and free; asking the panel costs two nibble reads per disp_sym
     }
 }
 
-impl HD44780Util for ParallelLcd1602 {
+impl<T: HD44780Kernel + Lcd1602 + ?Sized> HD44780Util for T { // ← one utility layer for every backend; only boot() differs
     // ========================== UTILITY ===============================
     // Partially based on HD44780U datasheet p40-41.
-    fn cgload(&mut self, data: [[u8; 8]; 7]) { // ← load 5x8 CGRAM symbols (0-5 LSB). Read from flash memory.
+    fn cgload(&mut self, data: [[u8; 8]; 7]) -> Result<(), LcdError> { // ← load 5x8 CGRAM symbols (0-5 LSB). Read from flash memory.
         // CGRAM addresses are 0b000000-0b001111, relevant CGRAM data is 5c x 8r = 40 bits.
-        for symind in 0..data.len() {
-            let sym = data[symind];
-            for symline in 0..sym.len() {
-                self.cgs((&symind << 3 | symline) as u8);
-                self.dtw(sym[symline]) // NOTE: MS3B irrelevant, but also masking is redundant.
+        for (symind, sym) in data.iter().enumerate() {
+            for (symline, row) in sym.iter().enumerate() {
+                self.cgs((symind << 3 | symline) as u8)?;
+                self.dtw(*row)?; // NOTE: MS3B irrelevant, but also masking is redundant.
             }
 
             // Protect 8th line for cursor
-            self.cgs((&symind << 3 | 0b000111usize) as u8);
-            self.dtw(0x0);
+            self.cgs((symind << 3 | 0b000111usize) as u8)?;
+            self.dtw(0x0)?;
         }
+
+        Ok(())
     }
 
-    fn init(&mut self) {
-        // See Figure 23 of Hitachi HD44780U datasheet; manual initialisation
-        arduino_hal::delay_ms(150);
-        self.cmb(&0b00_0011_0000);
-        arduino_hal::delay_ms(10);
-        self.cmb(&0b00_0011_0000);
-        arduino_hal::delay_us(150);
-        self.cmb(&0b00_0011_0000);
-        arduino_hal::delay_us(150);
-        self.cmd(&0b00_0011_1000); // DL=8D, N=2R, F=5x7
-        self.cmd(&0b00_0000_1000); // Display off
-        self.cmd(&0b00_0000_0001); // Display clear
-        self.cmd(&0b00_0000_0111); // I/D=inc, S=shift
-
-        ufmt::uwriteln!(&mut self.serial, "\n\nInitialised.\n\n");
-    }
-
-    fn affix(&mut self, row: u8, str: &str) { // heckin' keep calling things affix lul (lovely word)
-        let dd_addr = match row {
-            0 => 0x00,
-            1 => 0x40,
-            _ => 0x00
-        };
+    fn cgput(&mut self, slot: u8, rows: [u8; 8]) -> Result<(), LcdError> { // ← upload one glyph mid-draw without losing the DDRAM cursor
+        if slot as usize >= CGRAM_SLOTS {
+            return Err(LcdError::OutOfRange);
+        }
+
+        let ac = self.rac()?;
+        self.cgs(slot << 3)?;
+
+        for row in rows {
+            self.dtw(row)?; // ← AC auto-increments through the slot's 8 lines
+        }
+
+        self.dds(ac)
+    }
+
+    fn init(&mut self) -> Result<(), LcdError> {
+        self.boot()?; // ← bus-width handshake + function set
+
+        self.cmd(&0b00_0000_1000)?; // Display off
+        self.cmd(&0b00_0000_0001)?; // Display clear
+        self.cmd(&0b00_0000_0111)?; // I/D=inc, S=shift
+        self.cgload(CGRAM_ARROWS)?;
+        for (cgr, c) in CGR_CHARS {
+            self.glyphs().seat(cgr, c);
+        }
+        self.dds(0x00)?; // ← back to DDRAM, otherwise the next dtw lands in CGRAM
+        self.cmd(&0b00_0000_1100) // Display on, C=off, B=off
+    }
 
-        self.dds(dd_addr);
-        self.disp_str(str);
+    fn affix(&mut self, row: u8, str: &str) -> Result<(), LcdError> { // heckin' keep calling things affix lul (lovely word)
+        if row >= self.geometry().rows {
+            return Err(LcdError::OutOfRange);
+        }
+
+        let dd_addr = self.geometry().addr(row, 0);
+
+        self.dds(dd_addr)?;
+        self.disp_str(str)
     }
 
-    fn marque(&mut self, iters: u8, graceful: bool) {
-        // 40 chars per line
+    fn marque(&mut self, iters: u8, graceful: bool) -> Result<(), LcdError> {
+        // 40 chars per DDRAM line, whatever the panel shows of it
+        let span = self.geometry().span();
         for _ in 0..iters {
-            for i in 0..42 {
+            for i in 0..=span {
                 match i {
-                    0 | 42 => {
+                    0 => {
                         if graceful {
-                            arduino_hal::delay_ms(1000);
+                            self.dlm(1000);
                         }
                     }
 
                     _ => {
-                        self.cds(true, true);
-                        arduino_hal::delay_ms(200);
+                        self.cds(true, true)?;
+                        self.dlm(200);
                     }
                 }
             }
         }
-    }
 
-    fn demo(&mut self, row0: &str, row1: &str, iters: u8, graceful: bool) {
-        self.clr();
-        self.affix(0, row0);
-        self.affix(1, row1);
-        self.marque(iters, graceful);
+        Ok(())
     }
-}
 
-impl Lcd1602 for ParallelLcd1602 {
-    fn disp_char(&mut self, c: char) {
-        let map = self.mapper.get(&c);
+    fn demo(&mut self, row0: &str, row1: &str, iters: u8, graceful: bool) -> Result<(), LcdError> { // ← runs the whole show before reporting the first Unmappable
+        let mut unmapped = None;
+        self.clr()?;
+        soft(self.affix(0, row0), &mut unmapped)?;
+        soft(self.affix(1, row1), &mut unmapped)?;
+        self.marque(iters, graceful)?;
 
-        if map.is_some() {
-            self.disp_sym(*map.unwrap_or_else(|| &0b1111_1111));
-        } else {
-            ufmt::uwriteln!(&mut self.serial, "NOMAP => {}", c);
-        }
+        unmapped.map_or(Ok(()), |c| Err(LcdError::Unmappable(c)))
     }
 
-    fn disp_sym(&mut self, sym: u8) {
-        // 16 x 2 = 32B DDRAM
-        // TODO: DDRAM is already selected?
-        self.dtw(sym);
-    }
+    fn paint(&mut self, frame: &mut Frame, str: &str) -> Result<(), LcdError> { // ← disp_str, but into the frame; nothing reaches DDRAM until flush
+        let mut unmapped = reseat(self, frame)?;
+        if let Ok(loads) = self.glyphs().plan(str) {
+            for (slot, rows) in loads {
+                self.cgput(slot, rows)?;
+            }
+        }
 
-    fn disp_str(&mut self, str: &str) {
+        let mut syms = Vec::new();
         for c in str.chars() {
-            self.disp_char(c);
+            let codes = self.encode(c)?;
+            if codes.replaced {
+                unmapped.get_or_insert(c);
+            }
+            syms.extend(codes);
         }
-    }
-}
 
-impl ParallelLcd1602 {
-    pub fn new(rs: Pin<Output>, rw: Pin<Output>, en: Pin<Output>, db: [Pin<Output>; 8], serial: Usart<USART0, Pin<Input, PE0>, Pin<Output, PE1>>) -> ParallelLcd1602 {
-        Self { rs, rw, en, db, serial, mapper: {
-            let mut fhm = FcHashMap::new();
-            fhm.insert('↑', 0b1111_1111).unwrap(); // TODO UPDATE WITH CGRAM SYMBOLS WHEN IMPLEMENTED
-            fhm.insert('↓', 0b1111_1111).unwrap();
-            fhm.insert('↖', 0b1111_1111).unwrap();
-            fhm.insert('↗', 0b1111_1111).unwrap();
-            fhm.insert('↘', 0b1111_1111).unwrap();
-            fhm.insert('↙', 0b1111_1111).unwrap();
-
-            fhm.insert('▓', 0b1111_1111).unwrap();
-            fhm.insert('"', 0b0010_0010).unwrap();
-            fhm.insert('!', 0b0010_0001).unwrap(); // ← Based on JIS X 0201 with JIS X 0208 mappings for ktk
-            fhm.insert('#', 0b0010_0011).unwrap();
-            fhm.insert('$', 0b0010_0100).unwrap();
-            fhm.insert('%', 0b0010_0101).unwrap();
-            fhm.insert('&', 0b0010_0110).unwrap();
-            fhm.insert('\'', 0b0010_0111).unwrap();
-            fhm.insert('(', 0b0010_1000).unwrap();
-            fhm.insert(')', 0b0010_1001).unwrap();
-            fhm.insert('*', 0b0010_1010).unwrap();
-            fhm.insert('+', 0b0010_1011).unwrap();
-            fhm.insert(',', 0b0010_1100).unwrap();
-            fhm.insert('-', 0b0010_1101).unwrap();
-            fhm.insert('.', 0b0010_1110).unwrap();
-            fhm.insert('/', 0b0010_1101).unwrap();
-
-            fhm.insert('0', 0b0011_0000).unwrap();
-            fhm.insert('1', 0b0011_0001).unwrap();
-            fhm.insert('2', 0b0011_0010).unwrap();
-            fhm.insert('3', 0b0011_0011).unwrap();
-            fhm.insert('4', 0b0011_0100).unwrap();
-            fhm.insert('5', 0b0011_0101).unwrap();
-            fhm.insert('6', 0b0011_0110).unwrap();
-            fhm.insert('7', 0b0011_0111).unwrap();
-            fhm.insert('8', 0b0011_1000).unwrap();
-            fhm.insert('9', 0b0011_1001).unwrap();
-            fhm.insert(':', 0b0011_1010).unwrap();
-            fhm.insert(';', 0b0011_1011).unwrap();
-            fhm.insert('<', 0b0011_1100).unwrap();
-            fhm.insert('=', 0b0011_1101).unwrap();
-            fhm.insert('>', 0b0011_1110).unwrap();
-            fhm.insert('?', 0b0011_1101).unwrap();
-
-            fhm.insert('@', 0b0100_0000).unwrap();
-            fhm.insert('A', 0b0100_0001).unwrap();
-            fhm.insert('B', 0b0100_0010).unwrap();
-            fhm.insert('C', 0b0100_0011).unwrap();
-            fhm.insert('D', 0b0100_0100).unwrap();
-            fhm.insert('E', 0b0100_0101).unwrap();
-            fhm.insert('F', 0b0100_0110).unwrap();
-            fhm.insert('G', 0b0100_0111).unwrap();
-            fhm.insert('H', 0b0100_1000).unwrap();
-            fhm.insert('I', 0b0100_1001).unwrap();
-            fhm.insert('J', 0b0100_1010).unwrap();
-            fhm.insert('K', 0b0100_1011).unwrap();
-            fhm.insert('L', 0b0100_1100).unwrap();
-            fhm.insert('M', 0b0100_1101).unwrap();
-            fhm.insert('N', 0b0100_1110).unwrap();
-            fhm.insert('O', 0b0100_1111).unwrap();
-
-            fhm.insert('P', 0b0101_0000).unwrap();
-            fhm.insert('Q', 0b0101_0001).unwrap();
-            fhm.insert('R', 0b0101_0010).unwrap();
-            fhm.insert('S', 0b0101_0011).unwrap();
-            fhm.insert('T', 0b0101_0100).unwrap();
-            fhm.insert('U', 0b0101_0101).unwrap();
-            fhm.insert('V', 0b0101_0110).unwrap();
-            fhm.insert('W', 0b0101_0111).unwrap();
-            fhm.insert('X', 0b0101_1000).unwrap();
-            fhm.insert('Y', 0b0101_1001).unwrap();
-            fhm.insert('Z', 0b0101_1010).unwrap();
-            fhm.insert('[', 0b0101_1011).unwrap();
-            fhm.insert('¥', 0b0101_1100).unwrap();
-            fhm.insert(']', 0b0101_1101).unwrap();
-            fhm.insert('^', 0b0101_1110).unwrap();
-            fhm.insert('_', 0b0101_1111).unwrap();
-
-            fhm.insert('`', 0b0110_0000).unwrap();
-            fhm.insert('a', 0b0110_0001).unwrap();
-            fhm.insert('b', 0b0110_0010).unwrap();
-            fhm.insert('c', 0b0110_0011).unwrap();
-            fhm.insert('d', 0b0110_0100).unwrap();
-            fhm.insert('e', 0b0110_0101).unwrap();
-            fhm.insert('f', 0b0110_0110).unwrap();
-            fhm.insert('g', 0b0110_0111).unwrap();
-            fhm.insert('h', 0b0110_1000).unwrap();
-            fhm.insert('i', 0b0110_1001).unwrap();
-            fhm.insert('j', 0b0110_1010).unwrap();
-            fhm.insert('k', 0b0110_1011).unwrap();
-            fhm.insert('l', 0b0110_1100).unwrap();
-            fhm.insert('m', 0b0110_1101).unwrap();
-            fhm.insert('n', 0b0110_1110).unwrap();
-            fhm.insert('o', 0b0110_1111).unwrap();
-
-            fhm.insert('p', 0b0111_0000).unwrap();
-            fhm.insert('q', 0b0111_0001).unwrap();
-            fhm.insert('r', 0b0111_0010).unwrap();
-            fhm.insert('s', 0b0111_0011).unwrap();
-            fhm.insert('t', 0b0111_0100).unwrap();
-            fhm.insert('u', 0b0111_0101).unwrap();
-            fhm.insert('v', 0b0111_0110).unwrap();
-            fhm.insert('w', 0b0111_0111).unwrap();
-            fhm.insert('x', 0b0111_1000).unwrap();
-            fhm.insert('y', 0b0111_1001).unwrap();
-            fhm.insert('z', 0b0111_1010).unwrap();
-            fhm.insert('{', 0b0111_1011).unwrap();
-            fhm.insert('|', 0b0111_1100).unwrap();
-            fhm.insert('}', 0b0111_1101).unwrap();
-            fhm.insert('→', 0b0111_1110).unwrap();
-            fhm.insert('←', 0b0111_1111).unwrap();
-
-            // Skip 0b1000XXXX.
-
-            fhm.insert(' ', 0b1010_0000).unwrap();
-            fhm.insert('。', 0b1010_0001).unwrap();
-            fhm.insert('「', 0b1010_0010).unwrap();
-            fhm.insert('」', 0b1010_0011).unwrap();
-            fhm.insert('ヽ', 0b1010_0100).unwrap();
-            fhm.insert('・', 0b1010_0101).unwrap();
-            fhm.insert('ヲ', 0b1010_0110).unwrap();
-            fhm.insert('ァ', 0b1010_0111).unwrap();
-            fhm.insert('ィ', 0b1010_1000).unwrap();
-            fhm.insert('ゥ', 0b1010_1001).unwrap();
-            fhm.insert('ェ', 0b1010_1010).unwrap();
-            fhm.insert('ォ', 0b1010_1011).unwrap();
-            fhm.insert('ャ', 0b1010_1100).unwrap();
-            fhm.insert('ュ', 0b1010_1101).unwrap();
-            fhm.insert('ョ', 0b1010_1110).unwrap();
-            fhm.insert('ッ', 0b1010_1111).unwrap();
-
-            fhm.insert('ー', 0b1011_0000).unwrap();
-            fhm.insert('ア', 0b1011_0001).unwrap();
-            fhm.insert('イ', 0b1011_0010).unwrap();
-            fhm.insert('ウ', 0b1011_0011).unwrap();
-            fhm.insert('エ', 0b1011_0100).unwrap();
-            fhm.insert('オ', 0b1011_0101).unwrap();
-            fhm.insert('カ', 0b1011_0110).unwrap();
-            fhm.insert('キ', 0b1011_0111).unwrap();
-            fhm.insert('ク', 0b1011_1000).unwrap();
-            fhm.insert('ケ', 0b1011_1001).unwrap();
-            fhm.insert('コ', 0b1011_1010).unwrap();
-            fhm.insert('サ', 0b1011_1011).unwrap();
-            fhm.insert('シ', 0b1011_1100).unwrap();
-            fhm.insert('ス', 0b1011_1101).unwrap();
-            fhm.insert('セ', 0b1011_1110).unwrap();
-            fhm.insert('ソ', 0b1011_1111).unwrap();
-
-            fhm.insert('タ', 0b1100_0000).unwrap();
-            fhm.insert('チ', 0b1100_0001).unwrap();
-            fhm.insert('ツ', 0b1100_0010).unwrap();
-            fhm.insert('テ', 0b1100_0011).unwrap();
-            fhm.insert('ト', 0b1100_0100).unwrap();
-            fhm.insert('ナ', 0b1100_0101).unwrap();
-            fhm.insert('ニ', 0b1100_0110).unwrap();
-            fhm.insert('ヌ', 0b1100_0111).unwrap();
-            fhm.insert('ネ', 0b1100_1000).unwrap();
-            fhm.insert('ノ', 0b1100_1001).unwrap();
-            fhm.insert('ハ', 0b1100_1010).unwrap();
-            fhm.insert('ヒ', 0b1100_1011).unwrap();
-            fhm.insert('フ', 0b1100_1100).unwrap();
-            fhm.insert('ヘ', 0b1100_1101).unwrap();
-            fhm.insert('ホ', 0b1100_1110).unwrap();
-            fhm.insert('マ', 0b1100_1111).unwrap();
-
-            fhm.insert('ミ', 0b1101_0000).unwrap();
-            fhm.insert('ム', 0b1101_0001).unwrap();
-            fhm.insert('メ', 0b1101_0010).unwrap();
-            fhm.insert('モ', 0b1101_0011).unwrap();
-            fhm.insert('ヤ', 0b1101_0100).unwrap();
-            fhm.insert('ユ', 0b1101_0101).unwrap();
-            fhm.insert('ヨ', 0b1101_0110).unwrap();
-            fhm.insert('ラ', 0b1101_0111).unwrap();
-            fhm.insert('リ', 0b1101_1000).unwrap();
-            fhm.insert('ル', 0b1101_1001).unwrap();
-            fhm.insert('レ', 0b1101_1010).unwrap();
-            fhm.insert('ロ', 0b1101_1011).unwrap();
-            fhm.insert('ワ', 0b1101_1100).unwrap();
-            fhm.insert('ン', 0b1101_1101).unwrap();
-            fhm.insert('゛', 0b1101_1110).unwrap();
-            fhm.insert('゜', 0b1101_1111).unwrap();
-
-            // 10-bit wide symbols...
-
-            fhm.insert('α', 0b1110_0000).unwrap();
-            fhm.insert('ä', 0b1110_0001).unwrap();
-            fhm.insert('β', 0b1110_0010).unwrap();
-            fhm.insert('ε', 0b1110_0011).unwrap();
-            fhm.insert('μ', 0b1110_0100).unwrap();
-            fhm.insert('σ', 0b1110_0101).unwrap();
-            fhm.insert('ρ', 0b1110_0110).unwrap();
-            fhm.insert('ⓖ', 0b1110_0111).unwrap(); // kerned g
-            fhm.insert('√', 0b1110_1000).unwrap();
-            fhm.insert('ⁱ', 0b1110_1001).unwrap(); // ← superscript -1
-            fhm.insert('ⓙ', 0b1110_1010).unwrap(); // kerned j
-            fhm.insert('*', 0b1110_1011).unwrap();
-            fhm.insert('¢', 0b1110_1100).unwrap();
-            fhm.insert('₤', 0b1110_1101).unwrap(); // gnd upsidedown? lira?
-            fhm.insert('ñ', 0b1110_1110).unwrap();
-            fhm.insert('ö', 0b1110_1111).unwrap();
-
-            fhm.insert('ⓟ', 0b1111_0000).unwrap();  // kerned p
-            fhm.insert('ⓠ', 0b1111_0001).unwrap();  // kerned q
-            fhm.insert('θ', 0b1111_0010).unwrap();
-            fhm.insert('∞', 0b1111_0011).unwrap();
-            fhm.insert('Ω', 0b1111_0100).unwrap();
-            fhm.insert('ü', 0b1111_0101).unwrap();
-            fhm.insert('Σ', 0b1111_0110).unwrap();
-            fhm.insert('π', 0b1111_0111).unwrap();
-            fhm.insert('ⓧ', 0b1111_1000).unwrap(); // x-bar
-            fhm.insert('ⓨ', 0b1111_1001).unwrap(); // kerned y
-            fhm.insert('千', 0b1111_1010).unwrap();
-            fhm.insert('万', 0b1111_1011).unwrap();
-            fhm.insert('両', 0b1111_1100).unwrap();
-            fhm.insert('÷', 0b1111_1101).unwrap();
-            fhm.insert('▓', 0b1111_1111).unwrap();
-            fhm
-        }, anchor: 0, overcast: 0
+        for sym in syms {
+            frame.put(sym);
         }
+        frame.note(core::array::from_fn(|slot| self.glyphs().resident(slot as u8)));
+
+        unmapped.map_or(Ok(()), |c| Err(LcdError::Unmappable(c)))
     }
 
-    fn register(&mut self, mut byte: u8) { // ← write to DB register
-        ufmt::uwriteln!(&mut self.serial, "REGISTERING {:?}", bits8(byte));
-        for i in 0..8 {
-            let dbi = &mut self.db[i];
-            dbi.set_state(PinState::from(byte & 0x1 == 1)).expect("Could not set register pin state");
-            byte >>= 1;
-            // ufmt::uwriteln!(&mut self.serial, "REGUPD {:?}", bits8(byte));
+    fn flush(&mut self, frame: &mut Frame) -> Result<(), LcdError> { // ← send only what changed since the last flush
+        let lost = reseat(self, frame)?;
+        if !frame.synced() {
+            self.ems(true, false)?; // ← the frame owns addressing; a display that shifts on every write would drift away from it
+            self.ret()?;            // ← and undo whatever shift is left over
         }
-    }
 
-    fn check(&mut self) {
-        let binding = self.db.iter().map(|p| u8::from(p.is_set_high())).rev().collect::<Vec<_>>();
-        let ps: &[u8] = binding.as_slice();
-        ufmt::uwriteln!(&mut self.serial, "CHK: {} {} / {:?}\n", u8::from(self.rs.is_set_high()), u8::from(self.rw.is_set_high()), ps);
-    }
+        let geometry = frame.geometry();
+        let mut ac = None; // ← where DDRAM's address counter is known to be
+        for row in 0..geometry.rows {
+            let mut col = 0;
 
-    fn dbx<R: RangeBounds<usize> + core::slice::SliceIndex<[Pin<Output, Dynamic>], Output = [Pin<Output, Dynamic>]>>(&mut self, i: R) -> u8 { // ← utility for bitmasking ith register value. Range to save accesses if several needed.
-        let dbs: &[Pin<Output>] = self.db.get(i).expect("Could not index DB pins");
-        let mut x = 0u8;
+            while let Some((start, end)) = frame.run(row, col) {
+                let addr = geometry.addr(row, start);
+                if ac != Some(addr) {
+                    self.dds(addr)?;
+                }
 
-        for db in dbs {
-            x |= u8::from(db.is_set_high());
-            x <<= 1;
+                for c in start..end {
+                    self.dtw(frame.cell(row, c))?;
+                }
+
+                frame.commit(row, start, end); // ← per run, so a failed flush only resends what didn't make it
+                ac = Some(addr + (end - start));
+                col = end;
+            }
         }
 
-        x
-    }
+        if let Some((underline, blink)) = frame.cursor_change() {
+            self.dsw(true, underline, blink)?;
+        }
 
-    fn disp_symv(&mut self, symv: Vec<u8>) {
-        assert!(symv.iter().all(|b| b <= &0b11111111)); // Only accept 8-bit symbols
+        self.glyphs().release(); // ← no clr() in a frame's life, so what's pinned is exactly what it shows
+        self.glyphs().pin(frame.slots());
 
-        for sym in symv {
-            self.disp_sym(sym);
-            // TODO: need to change lines?
+        let (row, col) = frame.position();
+        let park = geometry.addr(row, col.min(geometry.cols - 1));
+        if ac != Some(park) {
+            self.dds(park)?; // ← keeps the (possibly visible) cursor where the app left it
         }
+
+        frame.settle();
+        lost.map_or(Ok(()), |c| Err(LcdError::Unmappable(c)))
     }
 
-    fn line(i: u8) {
-        assert!(i == 0 || i == 1);
+    fn layout(&mut self, str: &str, align: Align) -> Result<Layout, LcdError> { // ← word-wrap str to the panel's width
+        if let Ok(loads) = self.glyphs().plan(str) {
+            for (slot, rows) in loads {
+                self.cgput(slot, rows)?;
+            }
+        }
+
+        let mut layout = Layout::new(self.geometry().cols, align);
+        for c in str.chars() {
+            match c {
+                ' ' => layout.space(),
+                '\n' => layout.newline(),
+                _ => {
+                    let codes = self.encode(c)?;
+                    let mut glyph = [0u8; 6];
+                    let width = codes.width() as usize;
+                    for (i, sym) in codes.enumerate() {
+                        glyph[i] = sym;
+                    }
+                    layout.push(&glyph[..width]);
+                }
+            }
+        }
 
+        layout.finish();
+        Ok(layout)
+    }
 
+    fn leaf(&mut self, layout: &Layout, frame: &mut Frame, ms: u32, rounds: u8) -> Result<(), LcdError> { // ← page through on a timer; one page = one flush
+        let pages = layout.pages(frame.geometry().rows);
+        for _ in 0..rounds {
+            for page in 0..pages {
+                layout.render(frame, page);
+                self.flush(frame)?;
+                self.dlm(ms);
+            }
+        }
+
+        Ok(())
     }
 
-    fn bso(&mut self, fw: bool) {   // ← cyclic "bit shift" for current line.
-        // Read value @ 0, set DDRAM pointer +1, read/store and write @ 1.
-        for mut i in 0..8u8 { // note that Range<> does not implement storing as reverse; use the either crate
-            if !fw {
-                i = 8 - i;
+    fn encode_str(&mut self, str: &str) -> Result<Vec<u8>, LcdError> { // ← e.g. for Marquee::set_row
+        if let Ok(loads) = self.glyphs().plan(str) {
+            for (slot, rows) in loads {
+                self.cgput(slot, rows)?;
             }
+        }
 
-            self.dds(8 % i);
-            let mut sym = 0b0;
+        let mut codes = Vec::new();
+        for c in str.chars() {
+            codes.extend(self.encode(c)?);
+        }
+
+        Ok(codes)
+    }
 
-            if i != 0 {
-                self.dtw(sym);
+    //                                                                                                        * ! **
+    fn amarque(&mut self, marquee: &mut Marquee, frame: &mut Frame, ms: u32) -> Result<(), LcdError> {  // imaginary words, in my code??!! o.o
+        // Blocking convenience for demos; anything that wants to read keys meanwhile calls marquee.tick itself.
+        let mut now = 0;
+        while now < ms {
+            if marquee.tick(now, frame) {
+                self.flush(frame)?;
             }
 
-            sym = {
-                self.dtr();
-                self.dbx(0..8)
-            };
+            self.dlm(AMARQUE_TICK);
+            now += AMARQUE_TICK;
         }
+
+        Ok(())
+    }
+
+    fn bar(&mut self, frame: &mut Frame, bar: &Bar, value: u16, max: u16) -> Result<(), LcdError> { // ← e.g. 3/9 stops
+        frame.at(bar.row, bar.col);
+        self.paint(frame, &bar.text(value, max))
     }
 
-    fn bsd(&mut self, dist: u8) {
-        // DDRAM is 0x0-0x20 (0-32)
-        // Read DDRAM at 0x0, Set offset DDRAM pointer, read + store then write.
+    fn gauge(&mut self, frame: &mut Frame, gauge: &Gauge, value: u16, max: u16) -> Result<(), LcdError> {
+        let mut unmapped = None;
+        for (i, c) in gauge.cells(value, max).into_iter().enumerate() {
+            frame.at(gauge.row + i as u8, gauge.col);
+            soft(self.paint(frame, c.encode_utf8(&mut [0u8; 4])), &mut unmapped)?;
+        }
+
+        unmapped.map_or(Ok(()), |c| Err(LcdError::Unmappable(c)))
     }
-    fn marquee(&mut self, ms: u32) {
-        loop {
-            self.cds(true, true);
-            arduino_hal::delay_ms(ms);
+
+    fn big(&mut self, frame: &mut Frame, row: u8, col: u8, str: &str) -> Result<(), LcdError> { // ← two rows tall: row and row + 1
+        if row + 1 >= frame.geometry().rows {
+            return Err(LcdError::OutOfRange);
         }
+
+        let big = bignum::compose(str);
+        let mut unmapped = big.unmapped;
+        frame.at(row, col);
+        soft(self.paint(frame, &big.top), &mut unmapped)?;
+        frame.at(row + 1, col);
+        soft(self.paint(frame, &big.bottom), &mut unmapped)?;
+
+        unmapped.map_or(Ok(()), |c| Err(LcdError::Unmappable(c)))
     }
 
-    fn timer(&mut self, msg: &str, duration: u16) {
+    fn timer(&mut self, msg: &str, duration: u16) -> Result<(), LcdError> { // ← mm:ss countdown in big digits, msg alongside
+        let mut frame = Frame::new(self.geometry());
         let mut bomb = duration;
         let mut blink = true;
-        while bomb > 0 {
-            self.disp_str(&*format!("{:02}", bomb / 60));
+        let mut unmapped = None; // ← a funny char in msg shouldn't cancel the countdown
 
-            let blc = if blink { ':' } else { ' ' };
+        if self.geometry().rows > 2 {
+            frame.at(2, 0);
+            soft(self.paint(&mut frame, msg), &mut unmapped)?; // ← painted once; flush only ever resends the digits that tick
+        } else {
+            frame.at(0, 0);
+            soft(self.paint(&mut frame, msg), &mut unmapped)?; // ← no room under the big digits on a 2-row panel; show it up front instead
+            self.flush(&mut frame)?;
+            self.dlm(2000);
+            frame.clear();
+        }
+
+        let col = self.geometry().cols.saturating_sub(bignum::compose("00:00").width) / 2; // ← every mm:ss is as wide, blinking colon included
+        while bomb > 0 {
+            let blc = if blink { b':' } else { b' ' };
             blink = !blink;
-            self.disp_char(blc);
 
-            self.disp_str(&*format!("{:02}", bomb % 60));
+            let (mm, ss) = ((bomb / 60).min(99) as u8, (bomb % 60) as u8);
+            let time = [b'0' + mm / 10, b'0' + mm % 10, blc, b'0' + ss / 10, b'0' + ss % 10]; // ← mm:ss on the stack, not the heap
+            let time = core::str::from_utf8(&time).unwrap_or("");
+            soft(self.big(&mut frame, 0, col, time), &mut unmapped)?;
+            self.flush(&mut frame)?;
 
             bomb -= 1;
 
-            self.affix(1, msg);
-            self.dds(0);
+            self.dlm(670);
+        }
+
+        unmapped.map_or(Ok(()), |c| Err(LcdError::Unmappable(c)))
+    }
 
-            arduino_hal::delay_ms(670);
+    // bsd/bso/ls read the row back out of DDRAM, so they need a panel that can be read: the emulator, or a PCF8574
+    // backpack with RW wired. The parallel bus (output-only pins) and ADAFRUIT's MCP23008 answer NoReadback, before
+    // anything on screen has changed. Either way AC is left at the start of the row: it can't be put back blindly,
+    // since rac() doesn't say whether it pointed into DDRAM or CGRAM.
+    fn bsd(&mut self, row: u8, dist: i8, cyclic: bool) -> Result<(), LcdError> { // ← "bit shift" one row by dist cells (+ → rightward), read back from DDRAM
+        if row >= self.geometry().rows {
+            return Err(LcdError::OutOfRange);
         }
+
+        let (base, len) = self.geometry().lane(row);
+        let mut line = [BLANK; 40];
+
+        self.dds(base)?;
+        for cell in line.iter_mut().take(len as usize) {
+            *cell = self.dtr()?; // ← AC walks the lane by itself; the first one fails on a write-only panel
+        }
+
+        self.dds(base)?;
+        for i in 0..len as i16 {
+            let from = i - dist as i16;
+            let sym = match cyclic {
+                true => line[from.rem_euclid(len as i16) as usize],
+                false if (0..len as i16).contains(&from) => line[from as usize],
+                false => BLANK // ← one-shot: whatever falls off the end is gone
+            };
+            self.disp_sym(sym)?;
+        }
+
+        self.dds(base) // ← any Frame over this row is stale now
     }
 
-    //                                                                                                        * ! **
-    fn amarque(&mut self, iters: u8, style: MarqueStyle, fixstr: &str) {  // imaginary words, in my code??!! o.o
-        for _ in 0..iters {
-            for i in 0..42 {
-                match style {
-                    MarqueStyle::SoloHighR => {
-                        self.cds(true, true);
-                        self.affix(1, fixstr);
-                        arduino_hal::delay_ms(130);
-                    }
+    fn bso(&mut self, row: u8, fw: bool) -> Result<(), LcdError> { // ← cyclic, one cell
+        self.bsd(row, if fw { 1 } else { -1 }, true)
+    }
 
-                    _ => {}
-                }
+    fn ls(&mut self, row: u8, rightward: bool, ms: u32, laps: u8) -> Result<(), LcdError> { // ← marque for a single row; the others stay put
+        let (_, len) = self.geometry().lane(row);
+
+        for _ in 0..laps {
+            for _ in 0..len {
+                self.bso(row, rightward)?;
+                self.dlm(ms);
             }
         }
+
+        Ok(())
+    }
+}
+
+impl<P: StatefulOutputPin, D: DelayNs, L: uWrite, B: SetDutyCycle> Lcd1602 for ParallelLcd1602<P, D, L, B> {
+    fn state(&mut self) -> (&mut GlyphBank, CgRom, u8) {
+        (&mut self.glyphs, self.rom, self.replacement)
+    }
+
+    fn set_rom(&mut self, rom: CgRom) {
+        self.rom = rom;
+        self.replacement = translit::default_replacement(rom);
+    }
+
+    fn set_replacement(&mut self, sym: u8) {
+        self.replacement = sym;
     }
 
-    // pub fn ls(&mut self, row: u8, rightward: bool) {  // Line shift
-    //     let offset = row * 0x40; // Row is either 0 or 1
-    //     let mut temp = 0u8;
-    //
-    //     if rightward {
-    //         let mut prev = {
-    //             self.dds(offset + 0x27);
-    //             self.dtr()
-    //         };
-    //
-    //         for i in offset..=(offset + 0x27) {
-    //             temp = {
-    //                 self.dds(i);
-    //                 self.dtr()
-    //             };
-    //
-    //             self.dtw(prev);
-    //             prev = temp;
-    //         }
-    //     } else {
-    //         let mut prev = {
-    //             self.dds(offset + 1);
-    //             self.dtr()
-    //         };
-    //
-    //         for i in offset..=(offset + 0x27) {
-    //             let oi = (offset + 0x27) - i;
-    //
-    //             temp = {
-    //                 self.dds(oi);
-    //                 self.dtr()
-    //             };
-    //
-    //             self.dtw(prev);
-    //             prev = temp;
-    //         }
-    //     }
-    // }
-
-
-    // pub fn map_str(str: &str) -> [u8; ]
+    fn geometry(&self) -> Geometry {
+        self.geometry
+    }
+
+    fn set_geometry(&mut self, geometry: Geometry) {
+        self.geometry = geometry;
+    }
+
+    fn miss(&mut self, miss: Miss) {
+        report(&mut self.log, miss);
+    }
 }
 
-impl I2CLcd1602 {
-    pub fn new(i2c: I2c, target: u8, serial: Usart<USART0, Pin<Input, PE0>, Pin<Output, PE1>>) -> I2CLcd1602 {
-        Self { i2c, target, buf: 0u8, serial, mapper: {
-            let mut fhm = FcHashMap::new();
-            fhm.insert('↑', 0b1111_1111).unwrap(); // TODO UPDATE WITH CGRAM SYMBOLS WHEN IMPLEMENTED
-            fhm.insert('↓', 0b1111_1111).unwrap();
-            fhm.insert('↖', 0b1111_1111).unwrap();
-            fhm.insert('↗', 0b1111_1111).unwrap();
-            fhm.insert('↘', 0b1111_1111).unwrap();
-            fhm.insert('↙', 0b1111_1111).unwrap();
-
-            fhm.insert('▓', 0b1111_1111).unwrap();
-            fhm.insert('"', 0b0010_0010).unwrap();
-            fhm.insert('!', 0b0010_0001).unwrap(); // ← Based on JIS X 0201 with JIS X 0208 mappings for ktk
-            fhm.insert('#', 0b0010_0011).unwrap();
-            fhm.insert('$', 0b0010_0100).unwrap();
-            fhm.insert('%', 0b0010_0101).unwrap();
-            fhm.insert('&', 0b0010_0110).unwrap();
-            fhm.insert('\'', 0b0010_0111).unwrap();
-            fhm.insert('(', 0b0010_1000).unwrap();
-            fhm.insert(')', 0b0010_1001).unwrap();
-            fhm.insert('*', 0b0010_1010).unwrap();
-            fhm.insert('+', 0b0010_1011).unwrap();
-            fhm.insert(',', 0b0010_1100).unwrap();
-            fhm.insert('-', 0b0010_1101).unwrap();
-            fhm.insert('.', 0b0010_1110).unwrap();
-            fhm.insert('/', 0b0010_1101).unwrap();
-
-            fhm.insert('0', 0b0011_0000).unwrap();
-            fhm.insert('1', 0b0011_0001).unwrap();
-            fhm.insert('2', 0b0011_0010).unwrap();
-            fhm.insert('3', 0b0011_0011).unwrap();
-            fhm.insert('4', 0b0011_0100).unwrap();
-            fhm.insert('5', 0b0011_0101).unwrap();
-            fhm.insert('6', 0b0011_0110).unwrap();
-            fhm.insert('7', 0b0011_0111).unwrap();
-            fhm.insert('8', 0b0011_1000).unwrap();
-            fhm.insert('9', 0b0011_1001).unwrap();
-            fhm.insert(':', 0b0011_1010).unwrap();
-            fhm.insert(';', 0b0011_1011).unwrap();
-            fhm.insert('<', 0b0011_1100).unwrap();
-            fhm.insert('=', 0b0011_1101).unwrap();
-            fhm.insert('>', 0b0011_1110).unwrap();
-            fhm.insert('?', 0b0011_1101).unwrap();
-
-            fhm.insert('@', 0b0100_0000).unwrap();
-            fhm.insert('A', 0b0100_0001).unwrap();
-            fhm.insert('B', 0b0100_0010).unwrap();
-            fhm.insert('C', 0b0100_0011).unwrap();
-            fhm.insert('D', 0b0100_0100).unwrap();
-            fhm.insert('E', 0b0100_0101).unwrap();
-            fhm.insert('F', 0b0100_0110).unwrap();
-            fhm.insert('G', 0b0100_0111).unwrap();
-            fhm.insert('H', 0b0100_1000).unwrap();
-            fhm.insert('I', 0b0100_1001).unwrap();
-            fhm.insert('J', 0b0100_1010).unwrap();
-            fhm.insert('K', 0b0100_1011).unwrap();
-            fhm.insert('L', 0b0100_1100).unwrap();
-            fhm.insert('M', 0b0100_1101).unwrap();
-            fhm.insert('N', 0b0100_1110).unwrap();
-            fhm.insert('O', 0b0100_1111).unwrap();
-
-            fhm.insert('P', 0b0101_0000).unwrap();
-            fhm.insert('Q', 0b0101_0001).unwrap();
-            fhm.insert('R', 0b0101_0010).unwrap();
-            fhm.insert('S', 0b0101_0011).unwrap();
-            fhm.insert('T', 0b0101_0100).unwrap();
-            fhm.insert('U', 0b0101_0101).unwrap();
-            fhm.insert('V', 0b0101_0110).unwrap();
-            fhm.insert('W', 0b0101_0111).unwrap();
-            fhm.insert('X', 0b0101_1000).unwrap();
-            fhm.insert('Y', 0b0101_1001).unwrap();
-            fhm.insert('Z', 0b0101_1010).unwrap();
-            fhm.insert('[', 0b0101_1011).unwrap();
-            fhm.insert('¥', 0b0101_1100).unwrap();
-            fhm.insert(']', 0b0101_1101).unwrap();
-            fhm.insert('^', 0b0101_1110).unwrap();
-            fhm.insert('_', 0b0101_1111).unwrap();
-
-            fhm.insert('`', 0b0110_0000).unwrap();
-            fhm.insert('a', 0b0110_0001).unwrap();
-            fhm.insert('b', 0b0110_0010).unwrap();
-            fhm.insert('c', 0b0110_0011).unwrap();
-            fhm.insert('d', 0b0110_0100).unwrap();
-            fhm.insert('e', 0b0110_0101).unwrap();
-            fhm.insert('f', 0b0110_0110).unwrap();
-            fhm.insert('g', 0b0110_0111).unwrap();
-            fhm.insert('h', 0b0110_1000).unwrap();
-            fhm.insert('i', 0b0110_1001).unwrap();
-            fhm.insert('j', 0b0110_1010).unwrap();
-            fhm.insert('k', 0b0110_1011).unwrap();
-            fhm.insert('l', 0b0110_1100).unwrap();
-            fhm.insert('m', 0b0110_1101).unwrap();
-            fhm.insert('n', 0b0110_1110).unwrap();
-            fhm.insert('o', 0b0110_1111).unwrap();
-
-            fhm.insert('p', 0b0111_0000).unwrap();
-            fhm.insert('q', 0b0111_0001).unwrap();
-            fhm.insert('r', 0b0111_0010).unwrap();
-            fhm.insert('s', 0b0111_0011).unwrap();
-            fhm.insert('t', 0b0111_0100).unwrap();
-            fhm.insert('u', 0b0111_0101).unwrap();
-            fhm.insert('v', 0b0111_0110).unwrap();
-            fhm.insert('w', 0b0111_0111).unwrap();
-            fhm.insert('x', 0b0111_1000).unwrap();
-            fhm.insert('y', 0b0111_1001).unwrap();
-            fhm.insert('z', 0b0111_1010).unwrap();
-            fhm.insert('{', 0b0111_1011).unwrap();
-            fhm.insert('|', 0b0111_1100).unwrap();
-            fhm.insert('}', 0b0111_1101).unwrap();
-            fhm.insert('→', 0b0111_1110).unwrap();
-            fhm.insert('←', 0b0111_1111).unwrap();
-
-            // Skip 0b1000XXXX.
-
-            fhm.insert(' ', 0b1010_0000).unwrap();
-            fhm.insert('。', 0b1010_0001).unwrap();
-            fhm.insert('「', 0b1010_0010).unwrap();
-            fhm.insert('」', 0b1010_0011).unwrap();
-            fhm.insert('ヽ', 0b1010_0100).unwrap();
-            fhm.insert('・', 0b1010_0101).unwrap();
-            fhm.insert('ヲ', 0b1010_0110).unwrap();
-            fhm.insert('ァ', 0b1010_0111).unwrap();
-            fhm.insert('ィ', 0b1010_1000).unwrap();
-            fhm.insert('ゥ', 0b1010_1001).unwrap();
-            fhm.insert('ェ', 0b1010_1010).unwrap();
-            fhm.insert('ォ', 0b1010_1011).unwrap();
-            fhm.insert('ャ', 0b1010_1100).unwrap();
-            fhm.insert('ュ', 0b1010_1101).unwrap();
-            fhm.insert('ョ', 0b1010_1110).unwrap();
-            fhm.insert('ッ', 0b1010_1111).unwrap();
-
-            fhm.insert('ー', 0b1011_0000).unwrap();
-            fhm.insert('ア', 0b1011_0001).unwrap();
-            fhm.insert('イ', 0b1011_0010).unwrap();
-            fhm.insert('ウ', 0b1011_0011).unwrap();
-            fhm.insert('エ', 0b1011_0100).unwrap();
-            fhm.insert('オ', 0b1011_0101).unwrap();
-            fhm.insert('カ', 0b1011_0110).unwrap();
-            fhm.insert('キ', 0b1011_0111).unwrap();
-            fhm.insert('ク', 0b1011_1000).unwrap();
-            fhm.insert('ケ', 0b1011_1001).unwrap();
-            fhm.insert('コ', 0b1011_1010).unwrap();
-            fhm.insert('サ', 0b1011_1011).unwrap();
-            fhm.insert('シ', 0b1011_1100).unwrap();
-            fhm.insert('ス', 0b1011_1101).unwrap();
-            fhm.insert('セ', 0b1011_1110).unwrap();
-            fhm.insert('ソ', 0b1011_1111).unwrap();
-
-            fhm.insert('タ', 0b1100_0000).unwrap();
-            fhm.insert('チ', 0b1100_0001).unwrap();
-            fhm.insert('ツ', 0b1100_0010).unwrap();
-            fhm.insert('テ', 0b1100_0011).unwrap();
-            fhm.insert('ト', 0b1100_0100).unwrap();
-            fhm.insert('ナ', 0b1100_0101).unwrap();
-            fhm.insert('ニ', 0b1100_0110).unwrap();
-            fhm.insert('ヌ', 0b1100_0111).unwrap();
-            fhm.insert('ネ', 0b1100_1000).unwrap();
-            fhm.insert('ノ', 0b1100_1001).unwrap();
-            fhm.insert('ハ', 0b1100_1010).unwrap();
-            fhm.insert('ヒ', 0b1100_1011).unwrap();
-            fhm.insert('フ', 0b1100_1100).unwrap();
-            fhm.insert('ヘ', 0b1100_1101).unwrap();
-            fhm.insert('ホ', 0b1100_1110).unwrap();
-            fhm.insert('マ', 0b1100_1111).unwrap();
-
-            fhm.insert('ミ', 0b1101_0000).unwrap();
-            fhm.insert('ム', 0b1101_0001).unwrap();
-            fhm.insert('メ', 0b1101_0010).unwrap();
-            fhm.insert('モ', 0b1101_0011).unwrap();
-            fhm.insert('ヤ', 0b1101_0100).unwrap();
-            fhm.insert('ユ', 0b1101_0101).unwrap();
-            fhm.insert('ヨ', 0b1101_0110).unwrap();
-            fhm.insert('ラ', 0b1101_0111).unwrap();
-            fhm.insert('リ', 0b1101_1000).unwrap();
-            fhm.insert('ル', 0b1101_1001).unwrap();
-            fhm.insert('レ', 0b1101_1010).unwrap();
-            fhm.insert('ロ', 0b1101_1011).unwrap();
-            fhm.insert('ワ', 0b1101_1100).unwrap();
-            fhm.insert('ン', 0b1101_1101).unwrap();
-            fhm.insert('゛', 0b1101_1110).unwrap();
-            fhm.insert('゜', 0b1101_1111).unwrap();
-
-            // 10-bit wide symbols...
-
-            fhm.insert('α', 0b1110_0000).unwrap();
-            fhm.insert('ä', 0b1110_0001).unwrap();
-            fhm.insert('β', 0b1110_0010).unwrap();
-            fhm.insert('ε', 0b1110_0011).unwrap();
-            fhm.insert('μ', 0b1110_0100).unwrap();
-            fhm.insert('σ', 0b1110_0101).unwrap();
-            fhm.insert('ρ', 0b1110_0110).unwrap();
-            fhm.insert('ⓖ', 0b1110_0111).unwrap(); // kerned g
-            fhm.insert('√', 0b1110_1000).unwrap();
-            fhm.insert('ⁱ', 0b1110_1001).unwrap(); // ← superscript -1
-            fhm.insert('ⓙ', 0b1110_1010).unwrap(); // kerned j
-            fhm.insert('*', 0b1110_1011).unwrap();
-            fhm.insert('¢', 0b1110_1100).unwrap();
-            fhm.insert('₤', 0b1110_1101).unwrap(); // gnd upsidedown? lira?
-            fhm.insert('ñ', 0b1110_1110).unwrap();
-            fhm.insert('ö', 0b1110_1111).unwrap();
-
-            fhm.insert('ⓟ', 0b1111_0000).unwrap();  // kerned p
-            fhm.insert('ⓠ', 0b1111_0001).unwrap();  // kerned q
-            fhm.insert('θ', 0b1111_0010).unwrap();
-            fhm.insert('∞', 0b1111_0011).unwrap();
-            fhm.insert('Ω', 0b1111_0100).unwrap();
-            fhm.insert('ü', 0b1111_0101).unwrap();
-            fhm.insert('Σ', 0b1111_0110).unwrap();
-            fhm.insert('π', 0b1111_0111).unwrap();
-            fhm.insert('ⓧ', 0b1111_1000).unwrap(); // x-bar
-            fhm.insert('ⓨ', 0b1111_1001).unwrap(); // kerned y
-            fhm.insert('千', 0b1111_1010).unwrap();
-            fhm.insert('万', 0b1111_1011).unwrap();
-            fhm.insert('両', 0b1111_1100).unwrap();
-            fhm.insert('÷', 0b1111_1101).unwrap();
-            fhm.insert('▓', 0b1111_1111).unwrap();
-            fhm
-        }, anchor: 0, overcast: 0
+impl<P: StatefulOutputPin, D: DelayNs, L: uWrite> ParallelLcd1602<P, D, L> {
+    pub fn new(rs: P, rw: P, en: P, db: [P; 8], delay: D, log: L) -> ParallelLcd1602<P, D, L> {
+        Self::with_bus(rs, rw, en, DataBus::Octet(db), delay, log)
+    }
+
+    pub fn new_4bit(rs: P, rw: P, en: P, db: [P; 4], delay: D, log: L) -> ParallelLcd1602<P, D, L> { // ← db = [DB4, DB5, DB6, DB7]
+        Self::with_bus(rs, rw, en, DataBus::Nibble(db), delay, log)
+    }
+
+    pub fn with_bus(rs: P, rw: P, en: P, db: DataBus<P>, delay: D, log: L) -> ParallelLcd1602<P, D, L> {
+        Self { rs, rw, en, db, bl: NoBacklight, dimmer: AutoDim::new(), delay, log, glyphs: stock_bank(), rom: CgRom::A00, replacement: translit::default_replacement(CgRom::A00), geometry: Geometry::LCD1602, shadow: Shadow::new() }
+    }
+
+    pub fn with_backlight<B: SetDutyCycle>(self, bl: B) -> ParallelLcd1602<P, D, L, B> { // ← LED anode (pin 15) through a PWM-capable pin
+        let Self { rs, rw, en, db, dimmer, delay, log, glyphs, rom, replacement, geometry, shadow, .. } = self;
+        ParallelLcd1602 { rs, rw, en, db, bl, dimmer, delay, log, glyphs, rom, replacement, geometry, shadow }
+    }
+}
+
+impl<P: StatefulOutputPin, D: DelayNs, L: uWrite, B: SetDutyCycle> ParallelLcd1602<P, D, L, B> {
+
+    fn register(&mut self, mut byte: u8) -> Result<(), LcdError> { // ← write to DB register (only the low nibble if 4-bit)
+        trace!(self.log, "REGISTERING {:?}", bits8(byte));
+        for dbi in self.db.pins_mut() {
+            dbi.set_state(PinState::from(byte & 0x1 == 1)).map_err(LcdError::from_pin)?;
+            byte >>= 1;
+            // trace!(self.log, "REGUPD {:?}", bits8(byte));
         }
+
+        Ok(())
     }
 
-    fn cmbm(&mut self, bitmask: &u16, reg: &u16) { // Bitmask command... keeping in lieu as may have to add ParallelLCD1602's intrinsic bitmasking
-        self.cmb(&(reg & bitmask));
+    fn nib(&mut self, nibble: u8) -> Result<(), LcdError> { // ← lone nibble write (RS=0, RW=0), only for the 4-bit init handshake
+        self.rs.set_low().map_err(LcdError::from_pin)?;
+        self.rw.set_low().map_err(LcdError::from_pin)?;
+        self.register(nibble)?;
+        self.enp()
+    }
+
+    #[cfg(feature = "trace")]
+    fn check(&mut self) { // ← what the pins ended up at; builds a Vec every instruction, hence trace-only
+        let binding = self.db.pins_mut().iter_mut().map(|p| u8::from(p.is_set_high().unwrap_or(false))).rev().collect::<Vec<_>>();
+        let ps: &[u8] = binding.as_slice();
+        let (rs, rw) = (u8::from(self.rs.is_set_high().unwrap_or(false)), u8::from(self.rw.is_set_high().unwrap_or(false)));
+        trace!(self.log, "CHK: {} {} / {:?}\n", rs, rw, ps);
+    }
+
+    fn dbx<R: RangeBounds<usize> + core::slice::SliceIndex<[P], Output = [P]>>(&mut self, i: R) -> u8 { // ← utility for bitmasking ith register value. Range to save accesses if several needed.
+        let dbs: &mut [P] = self.db.pins_mut().get_mut(i).expect("Could not index DB pins");
+        let mut x = 0u8;
+
+        for db in dbs.iter_mut().rev() { // ← little endian, so walk MSB → LSB
+            x <<= 1;
+            x |= u8::from(db.is_set_high().unwrap_or(false));
+        }
+
+        x
     }
 }
 
-impl HD44780Util for I2CLcd1602 {
-    fn cgload(&mut self, data: [[u8; 8]; 7]) {
-        todo!()
+impl<P: StatefulOutputPin, D: DelayNs, L: uWrite, B: SetDutyCycle> Backlight for ParallelLcd1602<P, D, L, B> {
+    fn blw(&mut self, level: u8) -> Result<(), LcdError> {
+        self.bl.set_duty_cycle_fraction(level as u16, FULL as u16).map_err(LcdError::from_pin)
     }
 
-    // https://web.alfredstate.edu/faculty/weimandn/lcd/lcd_initialization/lcd_initialization_index.html
-    fn init(&mut self) {
-        arduino_hal::delay_ms(150);
-        self.iiw(&0b00_0011);
-        arduino_hal::delay_ms(10);
-        self.iiw(&0b00_0011);
-        arduino_hal::delay_us(150);
-        self.iiw(&0b00_0011);
-        arduino_hal::delay_us(150);
-        self.iiw(&0b00_0010);
-        arduino_hal::delay_us(100);
+    fn dimmer(&mut self) -> &mut AutoDim {
+        &mut self.dimmer
+    }
+}
 
-        self.iiw(&0b00_0010);
-        arduino_hal::delay_us(50);
-        self.iiw(&0b00_1000);
-        arduino_hal::delay_us(100);
+impl<P> DataBus<P> {
+    fn pins_mut(&mut self) -> &mut [P] {
+        match self {
+            DataBus::Octet(db) => db,
+            DataBus::Nibble(db) => db
+        }
+    }
 
-        self.iiw(&0b00_0000);
-        arduino_hal::delay_us(50);
-        self.iiw(&0b00_1000);
-        arduino_hal::delay_us(100);
+    fn is_nibble(&self) -> bool {
+        matches!(self, DataBus::Nibble(_))
+    }
+}
 
-        self.iiw(&0b00_0000);
-        arduino_hal::delay_us(50);
-        self.iiw(&0b00_0001);
-        arduino_hal::delay_ms(10);
+impl<I: I2c, D: DelayNs, L: uWrite> I2CLcd1602<I, D, L> {
+    pub fn new(i2c: I, target: u8, delay: D, log: L) -> I2CLcd1602<I, D, L> {
+        let mut lcd = Self { i2c, target, delay, buf: 0, dimmer: AutoDim::new(), map: PinMap::YWROBOT, poll: true, shadow: Shadow::new(), log, glyphs: stock_bank(), rom: CgRom::A00, replacement: translit::default_replacement(CgRom::A00), geometry: Geometry::LCD1602 };
+        lcd.set_pin_map(PinMap::YWROBOT); // ← backlight on from the very first frame
+        lcd
+    }
 
-        self.iiw(&0b00_0000);
-        arduino_hal::delay_us(50);
-        self.iiw(&0b00_0111);
-        arduino_hal::delay_us(100);
+    pub fn discover(mut i2c: I, delay: D, log: L) -> Result<I2CLcd1602<I, D, L>, LcdError> { // ← backpack at whichever address it answers on, with its expander's stock PinMap
+        let (target, expander) = backpack::probe(&mut i2c)?;
+        let mut lcd = Self::new(i2c, target, delay, log);
+        log!(lcd.log, "LCD @ {}", target);
+        lcd.set_pin_map(PinMap::stock(expander)); // ← an LCM1602 still needs set_pin_map; it looks just like a YwRobot from here
+        Ok(lcd)
+    }
 
-        self.iiw(&0b00_0000);
-        arduino_hal::delay_us(50);
-        self.iiw(&0b00_1111);
-        arduino_hal::delay_us(100);
-        // self.cmd(&0b00_0010_1000); // DL=4D, N=2R, F=5x7
-        // self.cmd(&0b00_0000_1000); // Display off
-        // self.cmd(&0b00_0000_0001); // Display clear
-        // self.cmd(&0b00_0000_0111); // I/D=inc, S=shift
-        // self.cmd(&0b00_0000_1111); // C=on, B=blink
+    pub fn set_polling(&mut self, poll: bool) { // ← false → fixed datasheet delays, e.g. if BF reads keep timing out
+        self.poll = poll && self.map.readable();
+    }
 
-        ufmt::uwriteln!(&mut self.serial, "\n\nInitialised.\n\n");
+    pub fn set_pin_map(&mut self, map: PinMap) { // ← before init(); YWROBOT by default
+        self.map = map;
+        self.poll = map.readable();
+        self.buf = 0;
+        if let Some(bl) = map.bl {
+            self.pin(bl, map.bl_state(true));
+        }
     }
 
-    fn affix(&mut self, row: u8, str: &str) {
-        todo!()
+    fn rw(&mut self, state: bool) {
+        if let Some(rw) = self.map.rw {
+            self.pin(rw, state);
+        }
     }
 
-    fn marque(&mut self, iters: u8, graceful: bool) {
-        todo!()
+    fn nbw(&mut self, nibble: u8) -> Result<(), LcdError> { // ← nibble write; latch D7-D4 (RS/RW/BL ride along from buf), then strobe E
+        for (i, d) in self.map.d.into_iter().enumerate() {
+            self.pin(d, nibble & (1 << i) != 0);
+        }
+        self.fin()?;
+        self.enp()
     }
 
-    fn demo(&mut self, row0: &str, row1: &str, iters: u8, graceful: bool) {
-        todo!()
+    fn nbr(&mut self) -> Result<u8, LcdError> { // ← nibble read; the LCD only drives D7-D4 while E is high
+        self.gin(self.map.e, true)?;
+        self.delay.delay_us(1);
+        let raw = self.iir()?;
+        self.gin(self.map.e, false)?;
+        self.delay.delay_us(1);
+
+        Ok(self.map.d.iter().enumerate().fold(0, |nibble, (i, d)| nibble | ((raw >> d) & 0x1) << i))
+    }
+
+    fn rdw(&mut self, rs: bool) -> Result<u8, LcdError> { // ← read word (RS=0 → BF + AC, RS=1 → DDRAM/CGRAM data)
+        // PCF8574 pins are quasi-bidirectional: they can only be read while released high, so park D7-D4 at 1
+        // before RW goes high and the LCD starts driving them. Then clock out upper + lower nibble.
+        if !self.map.readable() {
+            return Err(LcdError::NoReadback);
+        }
+
+        self.pin(self.map.rs, rs);
+        self.rw(true);
+        for d in self.map.d {
+            self.pin(d, true);
+        }
+        self.fin()?;
+
+        let nibbles = self.nbr().and_then(|upper| Ok((upper, self.nbr()?)));
+
+        self.rw(false); // ← never leave RW high; the next write would fight the LCD's drivers
+        self.fin()?;             //   (so this goes out even if a read above failed)
+
+        let (upper, lower) = nibbles?;
+        Ok((upper << 4) | lower)
     }
 }
 
-impl I2CBlOps for I2CLcd1602 {
-    fn iiw(&mut self, data: &u8) {
-       // ufmt::uwriteln!(&mut self.serial, ">> {:?}", bits8(*data));
-        self.i2c.write(self.target, &[*data]);
-        arduino_hal::delay_ms(8);
+impl<I: I2c, D: DelayNs, L: uWrite> Lcd1602 for I2CLcd1602<I, D, L> {
+    fn state(&mut self) -> (&mut GlyphBank, CgRom, u8) {
+        (&mut self.glyphs, self.rom, self.replacement)
     }
 
-    fn iir(&mut self) -> u8 {
-        let mut buf = [0u8; 1];
-        self.i2c.read(self.target, &mut buf);
-        buf[0]
+    fn set_rom(&mut self, rom: CgRom) {
+        self.rom = rom;
+        self.replacement = translit::default_replacement(rom);
     }
 
-    fn iirw(&mut self, data: &u8) -> u8 { // ← seems more efficient to use write_read compared to forcing manual 2x?
-        let mut buf = [0u8; 1];
-        self.i2c.write_read(self.target, &[*data], &mut buf);
-        buf[0]
+    fn set_replacement(&mut self, sym: u8) {
+        self.replacement = sym;
+    }
+
+    fn geometry(&self) -> Geometry {
+        self.geometry
+    }
+
+    fn set_geometry(&mut self, geometry: Geometry) {
+        self.geometry = geometry;
+    }
+
+    fn miss(&mut self, miss: Miss) {
+        report(&mut self.log, miss);
     }
 }
 
-impl Pinnable for I2CLcd1602 {
-    fn pin(&mut self, ind: u8, state: bool) { // push pin, "pinned"... pin.
-        self.buf |= u8::from(state) << ind;
+pub(crate) fn stream<T: Lcd1602>(lcd: &mut T, str: &str) -> Result<(), LcdError> { // ← disp_str without the up-front glyph plan (and its Vec)
+    for c in str.chars() {
+        match lcd.disp_char(c) {
+            Err(LcdError::Unmappable(_)) => {} // ← replacement's already on screen; don't abort the rest of the uwrite!
+            res => res?
+        }
     }
 
-    fn bin(&mut self, bitmask: u8, state: u8) { // bitmask push pin... bin.
-        self.buf &= state | !bitmask; // Affix all 0s to be set... must modify all bits in state that shouldn't be 0 to 1... all 0 bits in bitmask must be 1.
-        self.buf |= state & bitmask; // Affix all 1s to be set... must modify all bits in state that shouldn't be 1 to 0... all 0 bits in bitmask should be 0.
+    Ok(())
+}
+
+impl<P: StatefulOutputPin, D: DelayNs, L: uWrite, B: SetDutyCycle> uWrite for ParallelLcd1602<P, D, L, B> {
+    type Error = LcdError;
+
+    fn write_str(&mut self, s: &str) -> Result<(), LcdError> {
+        stream(self, s)
     }
+}
 
-    fn fin(&mut self) -> u8 { // flush pin... fín
-        let buf = self.buf;
-        self.buf = self.iirw(&buf); // ← important! make sure to poll pin values when pinning.
-        buf                              //              this is NOT for polling updated values for RDB or DTR.
+impl<P: StatefulOutputPin, D: DelayNs, L: uWrite, B: SetDutyCycle> fmt::Write for ParallelLcd1602<P, D, L, B> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        stream(self, s).map_err(|_| fmt::Error)
     }
+}
 
-    fn gin(&mut self, ind: u8, state: bool) { // go pin; consolidates if just changing single pin... gin
-        self.pin(ind, state);
-        self.fin();
+impl<I: I2c, D: DelayNs, L: uWrite> uWrite for I2CLcd1602<I, D, L> {
+    type Error = LcdError;
+
+    fn write_str(&mut self, s: &str) -> Result<(), LcdError> {
+        stream(self, s)
     }
 }
 
+impl<I: I2c, D: DelayNs, L: uWrite> fmt::Write for I2CLcd1602<I, D, L> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        stream(self, s).map_err(|_| fmt::Error)
+    }
+}
 
+impl<I: I2c, D: DelayNs, L: uWrite> Backlight for I2CLcd1602<I, D, L> {
+    fn blw(&mut self, level: u8) -> Result<(), LcdError> {
+        match self.map.bl {
+            Some(bl) => self.gin(bl, self.map.bl_state(level > 0)), // ← every later frame carries BL along from buf
+            None => Ok(())
+        }
+    }
 
+    fn dimmer(&mut self) -> &mut AutoDim {
+        &mut self.dimmer
+    }
+}
+
+impl<I: I2c, D: DelayNs, L: uWrite> I2CBlOps for I2CLcd1602<I, D, L> {
+    fn iiw(&mut self, data: &u8) -> Result<(), LcdError> {
+       // log!(self.log, ">> {:?}", bits8(*data));
+        match self.map.expander {
+            Expander::Pcf8574 => self.i2c.write(self.target, &[*data]),
+            Expander::Mcp23008 => self.i2c.write(self.target, &[MCP_GPIO, *data])
+        }.map_err(LcdError::from_i2c)
+    }
+
+    fn iir(&mut self) -> Result<u8, LcdError> {
+        let mut buf = [0u8; 1];
+        match self.map.expander {
+            Expander::Pcf8574 => self.i2c.read(self.target, &mut buf),
+            Expander::Mcp23008 => self.i2c.write_read(self.target, &[MCP_GPIO], &mut buf)
+        }.map_err(LcdError::from_i2c)?;
+        Ok(buf[0])
+    }
+}
 
+impl<I: I2c, D: DelayNs, L: uWrite> Pinnable for I2CLcd1602<I, D, L> {
+    fn pin(&mut self, ind: u8, state: bool) { // push pin, "pinned"... pin.
+        self.buf = (self.buf & !(1 << ind)) | (u8::from(state) << ind);
+    }
 
+    fn fin(&mut self) -> Result<u8, LcdError> { // flush pin... fín
+        let buf = self.buf;
+        self.iiw(&buf)?; // ← write-only! reading back here would latch whatever the LCD drives on D7-D4 into buf.
+        Ok(buf)          //   RDB/DTR poll through nbr instead.
+    }
 
+    fn gin(&mut self, ind: u8, state: bool) -> Result<(), LcdError> { // go pin; consolidates if just changing single pin... gin
+        self.pin(ind, state);
+        self.fin()?;
+        Ok(())
+    }
+}
 
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use crate::backpack::PinMap;
+    use crate::log::NoLog;
+    use crate::mock::{Bus, Delay, Pin, Pwm, Wires, DB, E, RS, RW};
+    use super::{Backlight, HD44780Kernel, HD44780Util, I2CLcd1602, LcdError, ParallelLcd1602, BUSY_TIMEOUT_US, CGRAM_ARROWS};
+
+    const HANDSHAKE: [u8; 4] = [0b0011, 0b0011, 0b0011, 0b0010]; // ← Figure 24, before the bus is 4 bits wide
+    const INIT: [u16; 3] = [0x008, 0x001, 0x007]; // ← display off, clear, entry mode (after function set)
+    const DONE: [u16; 2] = [0x080, 0x00C]; // ← back to DDRAM 0, display on
+
+    fn words(nibbles: &[(bool, bool, u8)]) -> Vec<u16> { // ← pair up (upper, lower) nibbles into 10-bit register words
+        nibbles.chunks(2).map(|pair| {
+            let ((rs, rw, hi), (rs2, rw2, lo)) = (pair[0], pair[1]);
+            assert_eq!((rs, rw), (rs2, rw2), "RS/RW changed between the two halves of one instruction");
+            (rs as u16) << 9 | (rw as u16) << 8 | ((hi & 0x0F) << 4 | (lo & 0x0F)) as u16
+        }).collect()
+    }
+
+    fn expander(bytes: &[u8], map: PinMap) -> Vec<(bool, bool, u8)> { // ← what the LCD latched, decoded from expander port writes
+        let bit = |byte: u8, pin: u8| byte & (1 << pin) != 0;
+        let mut nibbles = Vec::new();
+
+        for pair in bytes.windows(2) {
+            if bit(pair[0], map.e) && !bit(pair[1], map.e) {
+                let nibble = map.d.iter().enumerate().fold(0, |n, (i, &d)| n | (u8::from(bit(pair[1], d)) << i));
+                nibbles.push((bit(pair[1], map.rs), map.rw.is_some_and(|rw| bit(pair[1], rw)), nibble));
+            }
+        }
 
+        nibbles
+    }
 
+    fn assert_init(words: &[u16], fns: u16) {
+        assert_eq!(words[0], fns);
+        assert_eq!(words[1..4], INIT);
+        assert_eq!(words[4], 0x040); // ← cgload starts on slot 0, row 0
+        assert_eq!(words[5], 0x200 | CGRAM_ARROWS[0][0] as u16);
+        assert_eq!(words[words.len() - 2..], DONE);
+    }
+
+    fn parallel_4bit(wires: &Wires) -> ParallelLcd1602<Pin, Delay, NoLog> {
+        let db = [wires.pin(DB), wires.pin(DB + 1), wires.pin(DB + 2), wires.pin(DB + 3)];
+        ParallelLcd1602::new_4bit(wires.pin(RS), wires.pin(RW), wires.pin(E), db, Delay::default(), NoLog)
+    }
+
+    #[test]
+    fn parallel_8bit_init() {
+        let wires = Wires::default();
+        let db = core::array::from_fn(|i| wires.pin(DB + i));
+        let delay = Delay::default();
+        let mut lcd = ParallelLcd1602::new(wires.pin(RS), wires.pin(RW), wires.pin(E), db, delay.clone(), NoLog);
+        lcd.init().unwrap();
+
+        let latched = wires.latched();
+        assert!(latched[..3].iter().all(|&l| l == (false, false, 0x30)));
+        let words: Vec<u16> = latched[3..].iter().map(|&(rs, rw, db)| (rs as u16) << 9 | (rw as u16) << 8 | db as u16).collect();
+        assert_init(&words, 0x038); // ← DL=8D this time
+        assert!(delay.0.get() >= 150_000_000, "power-on wait skipped");
+    }
+
+    #[test]
+    fn parallel_4bit_init() {
+        let wires = Wires::default();
+        let mut lcd = parallel_4bit(&wires);
+        lcd.init().unwrap();
+
+        let latched = wires.latched();
+        assert_eq!(latched[..4].iter().map(|&(_, _, db)| db).collect::<Vec<_>>(), HANDSHAKE);
+        assert!(latched[..4].iter().all(|&(rs, rw, _)| !rs && !rw));
+        assert_init(&words(&latched[4..]), 0x028);
+    }
+
+    #[test]
+    fn parallel_4bit_data() {
+        let wires = Wires::default();
+        let mut lcd = parallel_4bit(&wires);
+        lcd.init().unwrap();
+        let before = wires.latched().len();
+
+        lcd.affix(1, "Hi").unwrap();
+        assert_eq!(words(&wires.latched()[before..]), [0x0C0, 0x248, 0x269]); // ← DDRAM 0x40, then 'H' 'i' with RS=1
+    }
+
+    #[test]
+    fn parallel_cgput() { // ← no readback on this bus: AC comes from the shadow, and RW never goes high
+        let wires = Wires::default();
+        let mut lcd = parallel_4bit(&wires);
+        lcd.init().unwrap();
+        let before = wires.latched().len();
+
+        lcd.affix(1, "Hi▌").unwrap();
+        let latched = &wires.latched()[before..];
+        assert!(latched.iter().all(|&(_, rw, _)| !rw));
+        let words = words(latched);
+        assert_eq!(words[..2], [0x0C0, 0x070]); // ← disp_str uploads first: CGRAM slot 6, the first one init left free
+        assert_eq!(words[10..], [0x0C0, 0x248, 0x269, 0x206]); // ← back to DDRAM 0x40 from the shadow, then "Hi" and the glyph
+        assert_eq!(lcd.dtr(), Err(LcdError::NoReadback));
+    }
+
+    #[test]
+    fn parallel_bsd() { // ← nothing to scroll from; bail before touching the row
+        let wires = Wires::default();
+        let mut lcd = parallel_4bit(&wires);
+        lcd.init().unwrap();
+        lcd.affix(1, "Hi").unwrap();
+        let before = wires.latched().len();
+
+        assert_eq!(lcd.bso(1, true), Err(LcdError::NoReadback));
+        assert_eq!(words(&wires.latched()[before..]), [0x0C0]);
+    }
+
+    #[test]
+    fn parallel_backlight() {
+        let wires = Wires::default();
+        let pwm = Pwm::default();
+        let mut lcd = parallel_4bit(&wires).with_backlight(pwm.clone());
+
+        lcd.set_backlight(128).unwrap();
+        assert_eq!(pwm.0.get(), 128);
+        lcd.bl(false).unwrap();
+        assert_eq!(pwm.0.get(), 0);
+    }
+
+    #[test]
+    fn auto_dim() { // ← the mock delay doubles as the main loop's clock
+        let wires = Wires::default();
+        let delay = Delay::default();
+        let pwm = Pwm::default();
+        let db = [wires.pin(DB), wires.pin(DB + 1), wires.pin(DB + 2), wires.pin(DB + 3)];
+        let mut lcd = ParallelLcd1602::new_4bit(wires.pin(RS), wires.pin(RW), wires.pin(E), db, delay.clone(), NoLog).with_backlight(pwm.clone());
+        let now = || (delay.0.get() / 1_000_000) as u32;
+
+        lcd.set_backlight(200).unwrap();
+        lcd.set_auto_dim(5000, 20);
+        lcd.wake(now()).unwrap();
+
+        lcd.dlm(4999);
+        lcd.idle(now()).unwrap();
+        assert_eq!(pwm.0.get(), 200);
+        lcd.dlm(1);
+        lcd.idle(now()).unwrap();
+        assert_eq!(pwm.0.get(), 20);
+
+        lcd.set_backlight(180).unwrap(); // ← not activity: stays dimmed, but wake() goes back to the new level
+        assert_eq!(pwm.0.get(), 20);
+        lcd.dlm(100);
+        lcd.wake(now()).unwrap();
+        assert_eq!(pwm.0.get(), 180);
+
+        lcd.dlm(4999);
+        lcd.idle(now()).unwrap(); // ← the clock restarted at the wake
+        assert_eq!(pwm.0.get(), 180);
+        lcd.dlm(1);
+        lcd.idle(now()).unwrap();
+        assert_eq!(pwm.0.get(), 20);
+    }
+
+    #[test]
+    fn i2c_auto_dim() { // ← an on/off backlight: dimming to 0 is lights out, until someone wakes it
+        let bus = Bus::default();
+        let mut lcd = I2CLcd1602::new(bus.clone(), 0x27, Delay::default(), NoLog);
+        lcd.init().unwrap();
+        lcd.set_auto_dim(1000, 0);
+        lcd.wake(0).unwrap();
+
+        lcd.idle(1000).unwrap();
+        assert_eq!(bus.bytes().last().map(|b| b & 0b1000), Some(0));
+        lcd.wake(1500).unwrap();
+        assert_eq!(bus.bytes().last().map(|b| b & 0b1000), Some(0b1000));
+    }
+
+    #[test]
+    fn i2c_ywrobot() {
+        let bus = Bus::default();
+        let mut lcd = I2CLcd1602::new(bus.clone(), 0x27, Delay::default(), NoLog);
+        lcd.init().unwrap();
+
+        let traffic = bus.0.borrow();
+        assert!(traffic.writes.iter().all(|(addr, bytes)| *addr == 0x27 && bytes.len() == 1));
+        assert!(traffic.reads > 0, "YwRobot has RW wired, BF should be polled");
+        drop(traffic);
+
+        let bytes = bus.bytes();
+        assert!(bytes.iter().all(|b| b & 0b1000 != 0), "backlight (P3) dropped somewhere");
+
+        let nibbles = expander(&bytes, PinMap::YWROBOT);
+        let handshake: Vec<u8> = nibbles.iter().take(4).map(|&(_, _, n)| n).collect();
+        assert_eq!(handshake, HANDSHAKE);
+
+        let writes: Vec<_> = nibbles[4..].iter().copied().filter(|&(_, rw, _)| !rw).collect(); // ← BF polls latch with RW high
+        assert_init(&words(&writes), 0x028);
+    }
+
+    #[test]
+    fn i2c_lcm1602() {
+        let bus = Bus::default();
+        let mut lcd = I2CLcd1602::new(bus.clone(), 0x3F, Delay::default(), NoLog);
+        lcd.set_pin_map(PinMap::LCM1602);
+        lcd.init().unwrap();
+
+        let bytes = bus.bytes();
+        assert!(bytes.iter().all(|b| b & 0x80 == 0), "backlight is active-low on P7");
+
+        let nibbles = expander(&bytes, PinMap::LCM1602);
+        let writes: Vec<_> = nibbles[4..].iter().copied().filter(|&(_, rw, _)| !rw).collect();
+        assert_init(&words(&writes), 0x028);
+    }
+
+    #[test]
+    fn i2c_adafruit() {
+        let bus = Bus::default();
+        let mut lcd = I2CLcd1602::new(bus.clone(), 0x20, Delay::default(), NoLog);
+        lcd.set_pin_map(PinMap::ADAFRUIT);
+        lcd.init().unwrap();
+
+        let traffic = bus.0.borrow();
+        assert_eq!(traffic.reads, 0, "RW is strapped to GND, nothing can be read");
+        let iodir = traffic.writes.iter().position(|(_, bytes)| bytes[..] == [0x00, 0x00]).expect("IODIR never set");
+        assert!(traffic.writes[iodir + 1..].iter().all(|(_, bytes)| bytes.len() == 2 && bytes[0] == 0x09));
+
+        let gpio: Vec<u8> = traffic.writes[iodir + 1..].iter().map(|(_, bytes)| bytes[1]).collect();
+        assert!(gpio.iter().all(|b| b & 0x80 != 0), "backlight (GP7) dropped somewhere");
+        assert_init(&words(&expander(&gpio, PinMap::ADAFRUIT)[4..]), 0x028);
+    }
+
+    #[test]
+    fn i2c_discover() { // ← an MCP23008 at 0x20 gets the Adafruit layout, not YwRobot's
+        let bus = Bus::default();
+        bus.0.borrow_mut().answers = vec![0x20];
+        bus.0.borrow_mut().mcp = vec![0x20];
+        let mut lcd = I2CLcd1602::discover(bus.clone(), Delay::default(), NoLog).unwrap();
+        lcd.init().unwrap();
+
+        let traffic = bus.0.borrow();
+        assert!(traffic.writes.iter().any(|(_, bytes)| bytes[..] == [0x00, 0x00]), "IODIR never set");
+        assert_eq!(traffic.reads, 2, "only probe() reads (scan + IPOL); RW is strapped to GND");
+    }
+
+    #[test]
+    fn i2c_busy() { // ← BF never clears: give up after BUSY_TIMEOUT_US rather than hang, and fixed delays still work
+        let bus = Bus::default();
+        bus.0.borrow_mut().busy = true;
+        let delay = Delay::default();
+        let mut lcd = I2CLcd1602::new(bus.clone(), 0x27, delay.clone(), NoLog);
+        assert_eq!(lcd.init(), Err(LcdError::BusyTimeout));
+        assert!(delay.0.get() >= BUSY_TIMEOUT_US as u64 * 1000);
+
+        lcd.set_polling(false);
+        let reads = bus.0.borrow().reads;
+        lcd.init().unwrap();
+        assert_eq!(bus.0.borrow().reads, reads);
+    }
+
+    #[test]
+    fn i2c_nack() {
+        let bus = Bus::default();
+        bus.0.borrow_mut().nack = true;
+        let mut lcd = I2CLcd1602::new(bus, 0x27, Delay::default(), NoLog);
+        assert_eq!(lcd.init(), Err(LcdError::Nack));
+    }
+}
@@ -0,0 +1,30 @@
+#![no_std]
+#![cfg_attr(target_arch = "avr", feature(asm_experimental_arch))] // ← cgrom::lpm reads flash with inline asm
+extern crate alloc;
+
+// Everything that doesn't need the board: the HD44780 drivers (generic over embedded-hal, so any pin/bus/delay
+// will do), the glyph/layout machinery, the emulator and routing. It builds for the host too, which is where the
+// tests run (cargo host-test); main.rs and the EEPROM/keypad bits stay in the AVR-only bin.
+
+pub mod lcd1602;
+pub mod cgram;
+pub mod cgrom;
+pub mod translit;
+pub mod frame;
+pub mod geometry;
+pub mod layout;
+pub mod anim;
+pub mod backlight;
+pub mod backpack;
+pub mod widget;
+pub mod bignum;
+pub mod emu;
+pub mod multi;
+pub mod log;
+pub mod gsearch;
+pub mod bitops;
+pub mod hash;
+
+#[cfg(test)]
+mod mock;
+
@@ -0,0 +1,66 @@
+use core::cell::RefCell;
+use core::convert::Infallible;
+use ufmt::uWrite;
+
+// Debug logging. A sink is anything ufmt can write to (uWrite); the displays and the EntryManager take one each,
+// and gsearch::two_opt borrows one per call, instead of owning the serial port. Hand them a Tap on one RefCell'd Usart and they all share it (main can
+// still use it too), or NoLog to say nothing. Building without the "log" feature compiles every log! away, format
+// strings included, which is where the flash actually goes. trace! is for the per-step chatter (every E strobe,
+// nibble and instruction, every 2-opt swap): it stays quiet unless the "trace" feature is on as well.
+
+pub struct NoLog;
+
+impl uWrite for NoLog {
+    type Error = Infallible;
+
+    fn write_str(&mut self, _: &str) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+pub struct Tap<'a, W>(&'a RefCell<W>); // ← a borrowed handle on a shared sink
+
+impl<'a, W> Tap<'a, W> {
+    pub fn new(sink: &'a RefCell<W>) -> Self {
+        Self(sink)
+    }
+}
+
+impl<W> Clone for Tap<'_, W> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<W> Copy for Tap<'_, W> {}
+
+impl<W: uWrite> uWrite for Tap<'_, W> {
+    type Error = W::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        match self.0.try_borrow_mut() {
+            Ok(mut sink) => sink.write_str(s),
+            Err(_) => Ok(()) // ← someone's mid-line on it already; drop ours rather than panic over a debug print
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! log { // ← uwriteln! that can't fail the caller and vanishes without the feature
+    ($sink:expr, $fmt:literal $(, $arg:expr)* $(,)?) => {{
+        #[cfg(feature = "log")]
+        { let _ = ufmt::uwriteln!(&mut $sink, $fmt $(, $arg)*); }
+        #[cfg(not(feature = "log"))]
+        { let _ = &$sink; $( let _ = &$arg; )* }
+    }};
+}
+
+#[macro_export]
+macro_rules! trace { // ← log! for once-per-strobe lines; they'd swamp the port (and the timing) otherwise
+    ($sink:expr, $fmt:literal $(, $arg:expr)* $(,)?) => {{
+        #[cfg(feature = "trace")]
+        { let _ = ufmt::uwriteln!(&mut $sink, $fmt $(, $arg)*); }
+        #[cfg(not(feature = "trace"))]
+        { let _ = &$sink; $( let _ = &$arg; )* }
+    }};
+}
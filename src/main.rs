@@ -1,30 +1,19 @@
 #![feature(cell_update)]
+#![feature(asm_experimental_arch)]
 #![no_std]
 #![no_main]
 extern crate alloc;
 
-mod lcd1602;
-mod gsearch;
 mod mempad;
-mod bitops;
 mod datmgt;
-mod hash;
 
-use crate::lcd1602::{HD44780Util, I2CLcd1602, MarqueStyle, ParallelLcd1602};
-use alloc::{format, vec};
-use alloc::vec::Vec;
-use arduino_hal::Eeprom;
+use flcavr2::lcd1602::{HD44780Util, I2CLcd1602};
 use arduino_hal::i2c::Direction;
-use arduino_hal::port::mode::Output;
-use arduino_hal::port::Pin;
-use arduino_hal::prelude::_unwrap_infallible_UnwrapInfallible;
 use embedded_alloc::LlffHeap as Heap;
-use embedded_hal::digital::OutputPin;
-use embedded_hal::i2c::I2c;
 use panic_halt as _;
-use ufmt::uwriteln;
-use crate::datmgt::EntryManager;
-use crate::gsearch::two_opt;
+use flcavr2::log;
+use flcavr2::log::Tap;
+use core::cell::RefCell;
 
 // use panic_halt as _;
 #[global_allocator]
@@ -79,12 +68,28 @@ fn main() -> ! {
         50000,
     );
 
-    i2c.i2cdetect(&mut serial, Direction::Write).expect("TODO: panic message");
-    let target = 0x27;
-    uwriteln!(serial, "{:?}", i2c.ping_device(target, Direction::Write));
+    if let Err(e) = i2c.i2cdetect(&mut serial, Direction::Write) {
+        log!(serial, "I2CDETECT FAILED => {:?}", e); // ← not fatal; the ping below says whether the backpack is there
+    }
 
-    let mut lcd = I2CLcd1602::new(i2c, target, serial);
-    lcd.init();
+    let serial = RefCell::new(serial); // ← shared from here on: the displays and the EntryManager each get a Tap
+    let mut led = pins.d13.into_output();
+    let mut lcd = match I2CLcd1602::discover(i2c, arduino_hal::Delay::new(), Tap::new(&serial)) { // ← YWROBOT, or ADAFRUIT if an MCP23008 answered
+        Ok(lcd) => lcd,
+        Err(e) => {
+            log!(Tap::new(&serial), "NO BACKPACK => {:?}", e);
+            led.set_high(); // ← the bus went with the failed probe; nothing left to drive
+            panic!(); // ← panic_halt parks it here
+        }
+    };
+    // let mut lcd = I2CLcd1602::new(i2c, 0x27, arduino_hal::Delay::new(), log::NoLog); // ← a quiet one, at a known address
+    // lcd.set_pin_map(PinMap::LCM1602); // ← PCF8574 boards wired differently; see backpack.rs for the layouts
+    // lcd.set_geometry(Geometry::LCD2004); // ← the 20x4 panel on the new cart
+    // lcd.set_auto_dim(60_000, 0); // ← night shift: lights out after a quiet minute, lcd.wake(now) on the next key press
+    if let Err(e) = lcd.init() {
+        log!(Tap::new(&serial), "LCD INIT FAILED => {:?}", e);
+        led.set_high(); // ← for when nobody's on the other end of the serial
+    }
 
     /*
      * For examples (and inspiration), head to
@@ -115,9 +120,17 @@ fn main() -> ! {
     // let db6: Pin<Output> = pins.d8.into_output().downgrade();
     // let db7: Pin<Output> = pins.d9.into_output().downgrade();
     // 
-    // let mut lcd = Lcd1602::new(rs, rw, en, [db0, db1, db2, db3, db4, db5, db6, db7], serial);
-    // let mut emgr: EntryManager = EntryManager::new(Eeprom::new(dp.EEPROM));
-    // emgr.load_sample(&lcd.mapper);
+    // let mut lcd = ParallelLcd1602::new(rs, rw, en, [db0, db1, db2, db3, db4, db5, db6, db7], arduino_hal::Delay::new(), Tap::new(&serial));
+    // // let mut lcd = ParallelLcd1602::new_4bit(rs, rw, en, [db4, db5, db6, db7], arduino_hal::Delay::new(), Tap::new(&serial)); // ← if only D4-D7 are wired
+    // // Both at once: the I2C one faces the driver, the parallel one the ward (see multi.rs)
+    // // let mut screens: Displays = Displays::new();
+    // // screens.add(Role::Driver, lcd_i2c);
+    // // screens.add(Role::Recipient, lcd);
+    // // screens.init();
+    // // screens.mirror().affix(0, "** PATH FOUND **"); // ← same text on both
+    // // screens.get(Role::Recipient).map(|lcd| lcd.affix(1, "Delivery: C148")); // ← just the one
+    // let mut emgr = EntryManager::new(Eeprom::new(dp.EEPROM), Tap::new(&serial));
+    // emgr.load_sample(CgRom::A00);
     // 
     // 
     // lcd.init();
@@ -128,10 +141,13 @@ fn main() -> ! {
     // match DEMO_TYPE {
     //     0 => {
     //         let mut tour = vec![0, 1, 2, 6, 9];
-    //         two_opt(&mut tour, 10);
+    //         two_opt(&mut tour, 10, &mut Tap::new(&serial));
     // 
     //         let stour = &format!("{:?}", tour);
-    //         lcd.demo("** PATH FOUND **", stour, 2, true);
+    //         lcd.clr();
+    //         lcd.affix(0, "** PATH FOUND **");
+    //         lcd.affix(1, stour);
+    //         lcd.ls(1, false, 200, 2); // ← header stays put, only the tour scrolls underneath. Needs readback: NoReadback on this bus, use a Marquee instead
     // 
     //         arduino_hal::delay_ms(400);
     //         //
@@ -154,12 +170,21 @@ fn main() -> ! {
     // 
     //         let (dictname, desc, cgrsym, dist) = emgr.read_pre(0, 1);
     // 
-    //         lcd.clr();
-    //         lcd.disp_sym(cgrsym);
-    //         lcd.disp_str(&format!(" {}m to {}", dist, dictname));
-    //         lcd.dds(0x40);
-    //         lcd.disp_symv(Vec::from(desc));
-    //         lcd.marque(3, true);
+    //         let mut frame = Frame::new(lcd.geometry());
+    //         frame.at(0, 0);
+    //         lcd.paint(&mut frame, cgr_char(cgrsym).unwrap_or('?').encode_utf8(&mut [0u8; 4])); // ← by char, so the arrow is re-uploaded if it was evicted
+    //         lcd.big(&mut frame, 0, 1, &format!("{}m", dist)); // ← readable from behind the cart
+    //         lcd.flush(&mut frame);
+    //         arduino_hal::delay_ms(2000);
+    //
+    //         frame.clear();
+    //         frame.at(0, 0);
+    //         lcd.paint(&mut frame, &format!("to {}", dictname));
+    //         lcd.flush(&mut frame);
+    //         arduino_hal::delay_ms(2000);
+    //
+    //         let page = Layout::from_codes(&desc, lcd.geometry().cols, Align::Left);
+    //         lcd.leaf(&page, &mut frame, 2500, 3);
     //         //
     //         arduino_hal::delay_ms(4000);
     //         // lcd.demo("Transmuting predat 1", "Writing postdat 1", 0, false);
@@ -173,7 +198,7 @@ fn main() -> ! {
     //         lcd.marque(1, false);
     //         lcd.dds(0x40);
     //         for i in 0..256 {
-    //             lcd.disp_str(&format!("{}", emgr.eepread(i)));
+    //             uwrite!(lcd, "{}", emgr.eepread(i)); // ← straight through the char mapper, no heap
     //         }
     //         lcd.clr();
     //         lcd.affix(0, "predat OK");
@@ -183,7 +208,7 @@ fn main() -> ! {
     //         lcd.marque(1, false);
     //         lcd.dds(0x40);
     //         for i in 0xC00..(0xC00 + 8) {
-    //             lcd.disp_str(&format!("{}", emgr.eepread(i)));
+    //             uwrite!(lcd, "{}", emgr.eepread(i)); // ← straight through the char mapper, no heap
     //             arduino_hal::delay_ms(100);
     //         }
     //         lcd.clr();
@@ -238,7 +263,7 @@ fn main() -> ! {
     // // lcd.marquee(400);
     // // lcd.marquee(600);
     loop {
-       // ufmt::uwriteln!(&mut serial, "OK...\r").unwrap_infallible();
+       // log!(Tap::new(&serial), "OK...\r");
         arduino_hal::delay_ms(5000);
     }
 
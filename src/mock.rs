@@ -0,0 +1,150 @@
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::{Cell, RefCell};
+use core::convert::Infallible;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{ErrorType, OutputPin, StatefulOutputPin};
+use embedded_hal::i2c::{self, I2c, Operation, SevenBitAddress};
+use embedded_hal::pwm::{self, SetDutyCycle};
+
+// Host stand-ins for the embedded-hal traits the backends are generic over. They don't pretend to be an HD44780;
+// they just remember what went over the wires, so tests can check the framing against the datasheet.
+
+pub const RS: usize = 0; // ← Wires line numbers; DB0 (or DB4 on a 4-bit bus) starts at DB
+pub const RW: usize = 1;
+pub const E: usize = 2;
+pub const DB: usize = 3;
+
+#[derive(Default)]
+pub struct Lines {
+    pub levels: [bool; DB + 8],
+    pub latched: Vec<(bool, bool, u8)> // ← (RS, RW, DB) at every falling edge of E, which is when the LCD samples
+}
+
+#[derive(Clone, Default)]
+pub struct Wires(pub Rc<RefCell<Lines>>);
+
+impl Wires {
+    pub fn pin(&self, line: usize) -> Pin {
+        Pin { line, wires: self.clone() }
+    }
+
+    pub fn latched(&self) -> Vec<(bool, bool, u8)> {
+        self.0.borrow().latched.clone()
+    }
+}
+
+pub struct Pin {
+    line: usize,
+    wires: Wires
+}
+
+impl ErrorType for Pin {
+    type Error = Infallible;
+}
+
+impl OutputPin for Pin {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        let mut lines = self.wires.0.borrow_mut();
+        if self.line == E && lines.levels[E] {
+            let db = (0..8).fold(0u8, |db, i| db | (u8::from(lines.levels[DB + i]) << i));
+            let (rs, rw) = (lines.levels[RS], lines.levels[RW]);
+            lines.latched.push((rs, rw, db));
+        }
+
+        lines.levels[self.line] = false;
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        self.wires.0.borrow_mut().levels[self.line] = true;
+        Ok(())
+    }
+}
+
+impl StatefulOutputPin for Pin {
+    fn is_set_high(&mut self) -> Result<bool, Infallible> {
+        Ok(self.wires.0.borrow().levels[self.line])
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Infallible> {
+        Ok(!self.wires.0.borrow().levels[self.line])
+    }
+}
+
+#[derive(Default)]
+pub struct Traffic {
+    pub writes: Vec<(u8, Vec<u8>)>, // ← (address, bytes) per write
+    pub reads: usize,
+    pub nack: bool,                 // ← nobody home at any address
+    pub answers: Vec<u8>,           // ← addresses that ACK; empty → all of them
+    pub mcp: Vec<u8>,               // ← addresses with an MCP23008 behind them; the rest are PCF8574s
+    pub busy: bool                  // ← P7-P4 read back high whatever was written: BF stuck on
+}
+
+#[derive(Clone, Default)]
+pub struct Bus(pub Rc<RefCell<Traffic>>);
+
+impl Bus {
+    pub fn bytes(&self) -> Vec<u8> { // ← everything written, flattened (one byte per PCF8574 write)
+        self.0.borrow().writes.iter().flat_map(|(_, bytes)| bytes.iter().copied()).collect()
+    }
+}
+
+impl i2c::ErrorType for Bus {
+    type Error = i2c::ErrorKind;
+}
+
+impl I2c<SevenBitAddress> for Bus {
+    fn transaction(&mut self, address: u8, operations: &mut [Operation<'_>]) -> Result<(), i2c::ErrorKind> {
+        let mut traffic = self.0.borrow_mut();
+        if traffic.nack || !(traffic.answers.is_empty() || traffic.answers.contains(&address)) {
+            return Err(i2c::ErrorKind::NoAcknowledge(i2c::NoAcknowledgeSource::Address));
+        }
+
+        let mut port = 0x00; // ← D7-D4 all low: never busy, AC/data 0
+        for op in operations {
+            match op {
+                Operation::Write(bytes) => {
+                    if !traffic.mcp.contains(&address) { // ← a PCF8574 reads back what it was just told, an MCP23008's registers are all 0
+                        port = bytes.last().copied().unwrap_or(port);
+                    }
+                    traffic.writes.push((address, bytes.to_vec()));
+                }
+                Operation::Read(buf) => {
+                    buf.fill(if traffic.busy { port | 0xF0 } else { port });
+                    traffic.reads += 1;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Delay(pub Rc<Cell<u64>>); // ← ns waited so far
+
+impl DelayNs for Delay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.0.set(self.0.get() + ns as u64);
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Pwm(pub Rc<Cell<u16>>); // ← last duty cycle, out of 255
+
+impl pwm::ErrorType for Pwm {
+    type Error = Infallible;
+}
+
+impl SetDutyCycle for Pwm {
+    fn max_duty_cycle(&self) -> u16 {
+        255
+    }
+
+    fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Infallible> {
+        self.0.set(duty);
+        Ok(())
+    }
+}
@@ -0,0 +1,298 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use crate::cgram::{GlyphBank, CGRAM_SLOTS};
+use crate::cgrom::CgRom;
+use crate::geometry::Geometry;
+use crate::lcd1602::{stock_bank, CGR_CHARS, HD44780Kernel, HD44780Util, LcdError, Lcd1602};
+use crate::translit;
+
+// Several panels on one cart. Displays owns them (any mix of backends, type-erased) and hands them out by role
+// for independent use; mirror() borrows all of them as one logical screen instead. The mirror is itself just
+// another HD44780Kernel that fans every instruction out, so all of HD44780Util works on it unchanged. It keeps its
+// own GlyphBank; whenever it rewrites a CGRAM slot, every panel's bank forgets that slot, so a panel picked up
+// again through get() re-uploads what it needs instead of drawing whatever the mirror left there. The other way
+// round, mirror() drops any slot a panel has since filled with a different glyph.
+
+pub trait Panel: HD44780Kernel + Lcd1602 {}
+
+impl<T: HD44780Kernel + Lcd1602> Panel for T {}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    Driver,    // ← faces whoever pushes the cart
+    Recipient  // ← faces the ward ("Delivery for C148")
+}
+
+pub struct Displays<'a, R = Role> { // ← 'a: whatever the panels borrow, e.g. a shared log::Tap
+    panels: Vec<(R, Box<dyn Panel + 'a>)>,
+    glyphs: GlyphBank, // ← the mirror's; see above
+    rom: CgRom,
+    replacement: u8,
+    geometry: Geometry
+}
+
+impl<R: Copy + PartialEq> Default for Displays<'_, R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, R: Copy + PartialEq> Displays<'a, R> {
+    pub fn new() -> Self {
+        Self { panels: Vec::new(), glyphs: stock_bank(), rom: CgRom::A00, replacement: translit::default_replacement(CgRom::A00), geometry: Geometry::LCD1602 }
+    }
+
+    pub fn add<P: Panel + 'a>(&mut self, role: R, panel: P) { // ← the first one added is the mirror's primary (reads, delays)
+        self.panels.push((role, Box::new(panel)));
+    }
+
+    pub fn get(&mut self, role: R) -> Option<&mut (dyn Panel + 'a)> {
+        self.panels.iter_mut().find(|(r, _)| *r == role).map(|(_, panel)| panel.as_mut())
+    }
+
+    pub fn init(&mut self) -> Result<(), LcdError> { // ← every panel, even if one of them fails
+        let mut res = Ok(());
+        for (_, panel) in self.panels.iter_mut() {
+            res = res.and(panel.init());
+        }
+
+        self.glyphs = stock_bank();
+        for (cgr, c) in CGR_CHARS { // ← init() put the arrows in every panel's CGRAM, so they're the mirror's too
+            self.glyphs.seat(cgr, c);
+        }
+
+        res
+    }
+
+    pub fn mirror(&mut self) -> Mirror<'_, 'a, R> {
+        for slot in 0..CGRAM_SLOTS as u8 { // ← panels drawing on their own since may have reloaded slots the mirror still counts on
+            let ours = self.glyphs.resident(slot);
+            if self.panels.iter_mut().any(|(_, panel)| panel.glyphs().resident(slot).is_some_and(|c| Some(c) != ours)) {
+                self.glyphs.forget(slot);
+            }
+        }
+
+        Mirror { displays: self }
+    }
+}
+
+pub struct Mirror<'m, 'a, R = Role> {
+    displays: &'m mut Displays<'a, R>
+}
+
+impl<'a, R> Mirror<'_, 'a, R> {
+    fn each(&mut self, mut f: impl FnMut(&mut (dyn Panel + 'a)) -> Result<(), LcdError>) -> Result<(), LcdError> { // ← first error wins, but nobody gets skipped
+        let mut res = Ok(());
+        for (_, panel) in self.displays.panels.iter_mut() {
+            res = res.and(f(panel.as_mut()));
+        }
+
+        res
+    }
+
+    fn primary(&mut self) -> Option<&mut (dyn Panel + 'a)> {
+        self.displays.panels.first_mut().map(|(_, panel)| panel.as_mut())
+    }
+}
+
+impl<R> HD44780Kernel for Mirror<'_, '_, R> {
+    fn polls(&self) -> bool {
+        false // ← never consulted; each panel waits out its own busy flag inside its cmd
+    }
+
+    fn enp(&mut self) -> Result<(), LcdError> {
+        self.each(|p| p.enp())
+    }
+
+    fn cmd(&mut self, reg: &u16) -> Result<(), LcdError> {
+        self.each(|p| p.cmd(reg))
+    }
+
+    fn cmb(&mut self, reg: &u16) -> Result<(), LcdError> {
+        self.each(|p| p.cmb(reg))
+    }
+
+    fn rdb(&mut self) -> Result<bool, LcdError> { // ← busy while any of them is
+        let mut busy = false;
+        self.each(|p| { busy |= p.rdb()?; Ok(()) })?;
+        Ok(busy)
+    }
+
+    fn clr(&mut self) -> Result<(), LcdError> {
+        self.displays.glyphs.release();
+        self.each(|p| p.clr())
+    }
+
+    fn ret(&mut self) -> Result<(), LcdError> {
+        self.each(|p| p.ret())
+    }
+
+    fn ems(&mut self, id: bool, s: bool) -> Result<(), LcdError> {
+        self.each(|p| p.ems(id, s))
+    }
+
+    fn dsw(&mut self, d: bool, c: bool, b: bool) -> Result<(), LcdError> {
+        self.each(|p| p.dsw(d, c, b))
+    }
+
+    fn cds(&mut self, sc: bool, rl: bool) -> Result<(), LcdError> {
+        self.each(|p| p.cds(sc, rl))
+    }
+
+    fn fns(&mut self, dl: bool, n: bool, f: bool) -> Result<(), LcdError> {
+        self.each(|p| p.fns(dl, n, f))
+    }
+
+    fn cgs(&mut self, addr: u8) -> Result<(), LcdError> { // ← the only way into CGRAM, so the one place to keep the panels' banks honest
+        self.each(|p| {
+            p.glyphs().forget(addr >> 3);
+            p.cgs(addr)
+        })
+    }
+
+    fn dds(&mut self, addr: u8) -> Result<(), LcdError> {
+        self.each(|p| p.dds(addr))
+    }
+
+    fn dtw(&mut self, data: u8) -> Result<(), LcdError> {
+        self.each(|p| p.dtw(data))
+    }
+
+    fn dtr(&mut self) -> Result<u8, LcdError> { // ← everyone reads (so every AC moves on), the primary's answer counts
+        let mut data = None;
+        self.each(|p| { let d = p.dtr()?; data.get_or_insert(d); Ok(()) })?;
+        data.ok_or(LcdError::NoReadback) // ← no panels, nothing to read
+    }
+
+    fn boot(&mut self) -> Result<(), LcdError> {
+        self.each(|p| p.boot())
+    }
+
+    fn dlm(&mut self, ms: u32) { // ← wait once, not once per panel
+        if let Some(p) = self.primary() {
+            p.dlm(ms);
+        }
+    }
+
+    fn dlu(&mut self, us: u32) {
+        if let Some(p) = self.primary() {
+            p.dlu(us);
+        }
+    }
+
+    fn rac(&mut self) -> Result<u8, LcdError> {
+        self.primary().ok_or(LcdError::NoReadback)?.rac()
+    }
+}
+
+impl<R> Lcd1602 for Mirror<'_, '_, R> {
+    fn state(&mut self) -> (&mut GlyphBank, CgRom, u8) {
+        (&mut self.displays.glyphs, self.displays.rom, self.displays.replacement)
+    }
+
+    fn set_rom(&mut self, rom: CgRom) { // ← mirroring assumes every panel has the same ROM; this sets it on all of them
+        self.displays.rom = rom;
+        self.displays.replacement = translit::default_replacement(rom);
+        for (_, panel) in self.displays.panels.iter_mut() {
+            panel.set_rom(rom);
+        }
+    }
+
+    fn set_replacement(&mut self, sym: u8) { // ← all of them too, so a panel on its own stands in the same way
+        self.displays.replacement = sym;
+        for (_, panel) in self.displays.panels.iter_mut() {
+            panel.set_replacement(sym);
+        }
+    }
+
+    fn geometry(&self) -> Geometry {
+        self.displays.geometry
+    }
+
+    fn set_geometry(&mut self, geometry: Geometry) { // ← the mirror draws into the smallest common window; set it to that
+        self.displays.geometry = geometry;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::emu::EmuLcd1602;
+    use crate::lcd1602::{HD44780Kernel, HD44780Util, LcdError, Lcd1602};
+    use super::{Displays, Role};
+
+    fn cart() -> Displays<'static> {
+        let mut displays = Displays::new();
+        displays.add(Role::Driver, EmuLcd1602::new());
+        displays.add(Role::Recipient, EmuLcd1602::new());
+        displays.init().unwrap();
+        displays
+    }
+
+    fn read(lcd: &mut (impl HD44780Kernel + ?Sized), addr: u8, len: usize) -> alloc::vec::Vec<u8> {
+        lcd.dds(addr).unwrap();
+        (0..len).map(|_| lcd.dtr().unwrap()).collect()
+    }
+
+    #[test]
+    fn mirror() {
+        let mut displays = cart();
+        displays.mirror().affix(0, "Hi ▌").unwrap();
+        displays.get(Role::Recipient).unwrap().affix(1, "C148").unwrap();
+
+        let driver = read(displays.get(Role::Driver).unwrap(), 0x00, 4);
+        assert_eq!(driver, read(displays.get(Role::Recipient).unwrap(), 0x00, 4));
+        assert_eq!(driver[..3], *b"Hi ");
+        assert_eq!(read(displays.get(Role::Driver).unwrap(), 0x40, 4), *b"    ");
+        assert_eq!(read(displays.get(Role::Recipient).unwrap(), 0x40, 4), *b"C148");
+    }
+
+    #[test]
+    fn panel_after_mirror() { // ← the mirror evicts an arrow on every panel; the panel on its own must notice
+        let mut displays = cart();
+        displays.mirror().affix(0, "▏▎▍").unwrap(); // ← slots 6 and 7, then the least recently used arrow
+
+        let panel = displays.get(Role::Driver).unwrap();
+        panel.affix(1, "↑").unwrap();
+        let code = read(panel, 0x40, 1)[0];
+        panel.cgs(code << 3).unwrap();
+        let rows: alloc::vec::Vec<u8> = (0..8).map(|_| panel.dtr().unwrap()).collect();
+
+        let mut fresh = EmuLcd1602::new();
+        fresh.init().unwrap();
+        assert_eq!(rows[..7], fresh.cgrows(0)[..7]); // ← ↑ as init uploads it
+    }
+
+    #[test]
+    fn mirror_after_panel() { // ← and the other way round: a panel evicts an arrow, the mirror must notice
+        let mut displays = cart();
+        displays.get(Role::Driver).unwrap().affix(1, "▏▎▍").unwrap();
+        displays.get(Role::Driver).unwrap().clr().unwrap();
+
+        displays.mirror().affix(0, "↑").unwrap();
+        let mut fresh = EmuLcd1602::new();
+        fresh.init().unwrap();
+        for role in [Role::Driver, Role::Recipient] {
+            let panel = displays.get(role).unwrap();
+            let code = read(panel, 0x00, 1)[0];
+            panel.cgs(code << 3).unwrap();
+            let rows: alloc::vec::Vec<u8> = (0..8).map(|_| panel.dtr().unwrap()).collect();
+            assert_eq!(rows[..7], fresh.cgrows(0)[..7], "{:?} shows the wrong glyph", role);
+        }
+    }
+
+    #[test]
+    fn replacement_reaches_panels() {
+        let mut displays = cart();
+        displays.mirror().set_replacement(b'#');
+        assert_eq!(displays.get(Role::Driver).unwrap().state().2, b'#');
+    }
+
+    #[test]
+    fn empty() {
+        let mut displays: Displays = Displays::new();
+        let mut mirror = displays.mirror();
+        assert_eq!(mirror.affix(0, "nobody"), Ok(()));
+        assert_eq!(mirror.dtr(), Err(LcdError::NoReadback));
+        assert_eq!(mirror.rac(), Err(LcdError::NoReadback));
+    }
+}
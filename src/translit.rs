@@ -0,0 +1,289 @@
+use crate::cgrom;
+use crate::cgrom::CgRom;
+
+// Fallback for chars the panel's ROM doesn't have. Tried in order:
+//   1. ROM as-is (incl. half-width katakana, which map 1:1 onto JIS X 0201 on A00)
+//   2. respell: hiragana → katakana, voiced kana → kana + ゛/゜, full-width ASCII → ASCII,
+//      smart quotes/dashes → ASCII, accented Latin → base letter, Cyrillic lookalikes → Latin
+//   3. the configurable replacement glyph
+// Respelled chars get one more pass (が → ガ → カ゛), anything still missing after that is replaced.
+// All rules are match arms rather than tables on purpose: code stays in flash, .rodata gets copied to SRAM.
+
+const MAX_CODES: usize = 6;
+
+pub struct Codes { // ← CGROM codes one char turns into (usually 1, at most MAX_CODES)
+    buf: [u8; MAX_CODES],
+    len: u8,
+    pos: u8,
+    pub replaced: bool // ← at least one code is the replacement glyph
+}
+
+impl Iterator for Codes {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.pos < self.len {
+            self.pos += 1;
+            Some(self.buf[self.pos as usize - 1])
+        } else {
+            None
+        }
+    }
+}
+
+impl Codes {
+    pub fn of(code: u8) -> Self { // ← the common case: char maps straight onto one code
+        let mut out = Self { buf: [0; MAX_CODES], len: 0, pos: 0, replaced: false };
+        out.push(code);
+        out
+    }
+
+    fn push(&mut self, code: u8) {
+        if (self.len as usize) < MAX_CODES {
+            self.buf[self.len as usize] = code;
+            self.len += 1;
+        }
+    }
+
+    pub fn width(&self) -> u8 { // ← how many DDRAM cells the char will take
+        self.len
+    }
+}
+
+pub fn default_replacement(rom: CgRom) -> u8 {
+    match rom {
+        CgRom::A00 => 0b1111_1111, // ← full block
+        CgRom::A02 => 0b0011_1111  // ← '?', A02's 0xFF is ÿ
+    }
+}
+
+pub fn codes(c: char, rom: CgRom, replacement: u8) -> Codes {
+    let mut out = Codes { buf: [0; MAX_CODES], len: 0, pos: 0, replaced: false };
+
+    if let Some(code) = direct(c, rom) {
+        out.push(code);
+        return out;
+    }
+
+    match respell(c) {
+        Some(first) => {
+            for r in first.chars() {
+                if let Some(code) = direct(r, rom) {
+                    out.push(code);
+                    continue;
+                }
+
+                match respell(r) {
+                    Some(second) => {
+                        for rr in second.chars() {
+                            match direct(rr, rom) {
+                                Some(code) => out.push(code),
+                                None => {
+                                    out.push(replacement);
+                                    out.replaced = true;
+                                }
+                            }
+                        }
+                    }
+                    None => {
+                        out.push(replacement);
+                        out.replaced = true;
+                    }
+                }
+            }
+        }
+        None => {
+            out.push(replacement);
+            out.replaced = true;
+        }
+    }
+
+    out
+}
+
+fn direct(c: char, rom: CgRom) -> Option<u8> {
+    match (c, rom) {
+        ('\u{FF61}'..='\u{FF9F}', CgRom::A00) => Some((c as u32 - 0xFF61 + 0xA1) as u8), // ← half-width ｡ ... ﾟ
+        _ => cgrom::lookup(c, rom)
+    }
+}
+
+struct Spelling { // ← up to 3 replacement chars
+    chars: [char; 3],
+    len: u8
+}
+
+impl Spelling {
+    fn one(a: char) -> Option<Self> {
+        Some(Self { chars: [a, '\0', '\0'], len: 1 })
+    }
+
+    fn two(a: char, b: char) -> Option<Self> {
+        Some(Self { chars: [a, b, '\0'], len: 2 })
+    }
+
+    fn three(a: char, b: char, c: char) -> Option<Self> {
+        Some(Self { chars: [a, b, c], len: 3 })
+    }
+
+    fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.chars.iter().take(self.len as usize).copied()
+    }
+}
+
+fn shift(c: char, by: i32) -> char {
+    char::from_u32((c as i32 + by) as u32).unwrap_or(c)
+}
+
+fn respell(c: char) -> Option<Spelling> {
+    match c {
+        // ===== Japanese =====
+        'ぁ'..='ゖ' => Spelling::one(shift(c, 0x60)), // ← hiragana → full-width katakana, same order
+        'ゝ' | 'ゞ' => Spelling::one(shift(c, 0x60)),
+        'ガ' | 'ギ' | 'グ' | 'ゲ' | 'ゴ' | 'ザ' | 'ジ' | 'ズ' | 'ゼ' | 'ゾ'
+        | 'ダ' | 'ヂ' | 'ヅ' | 'デ' | 'ド' | 'バ' | 'ビ' | 'ブ' | 'ベ' | 'ボ' => Spelling::two(shift(c, -1), '゛'),
+        'パ' | 'ピ' | 'プ' | 'ペ' | 'ポ' => Spelling::two(shift(c, -2), '゜'),
+        'ヴ' => Spelling::two('ウ', '゛'),
+        'ヷ' => Spelling::two('ワ', '゛'),
+        'ヺ' => Spelling::two('ヲ', '゛'),
+        'ヮ' => Spelling::one('ワ'),
+        'ヵ' => Spelling::one('カ'),
+        'ヶ' => Spelling::one('ケ'),
+        'ヰ' => Spelling::one('イ'),
+        'ヱ' => Spelling::one('エ'),
+        'ヾ' => Spelling::two('ヽ', '゛'),
+        '\u{3099}' => Spelling::one('゛'), // ← combining (dakuten)
+        '\u{309A}' => Spelling::one('゜'), // ← combining (handakuten)
+        '『' => Spelling::one('「'),
+        '』' => Spelling::one('」'),
+        '\u{3000}' => Spelling::one(' '),  // ← ideographic space
+        '！'..='～' => Spelling::one(shift(c, -0xFEE0)), // ← full-width ASCII
+
+        // ===== Punctuation =====
+        '‘' | '’' | '‚' | '‛' | '′' | '´' => Spelling::one('\''),
+        '“' | '”' | '„' | '‟' | '″' => Spelling::one('"'),
+        '‐' | '‑' | '‒' | '–' | '—' | '―' | '−' => Spelling::one('-'),
+        '…' => Spelling::three('.', '.', '.'),
+        '«' => Spelling::two('<', '<'),
+        '»' => Spelling::two('>', '>'),
+        '•' | '·' => Spelling::one('・'),
+        '×' => Spelling::one('x'),
+        '\u{00A0}' | '\u{2009}' | '\u{202F}' => Spelling::one(' '),
+        '€' => Spelling::three('E', 'U', 'R'),
+        '£' => Spelling::one('₤'),
+        '~' => Spelling::one('-'),
+
+        // ===== Latin =====
+        'À'..='Å' | 'Ā' | 'Ă' | 'Ą' => Spelling::one('A'),
+        'à'..='å' | 'ā' | 'ă' | 'ą' => Spelling::one('a'),
+        'Æ' => Spelling::two('A', 'E'),
+        'æ' => Spelling::two('a', 'e'),
+        'Ç' | 'Ć' | 'Ĉ' | 'Ċ' | 'Č' => Spelling::one('C'),
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => Spelling::one('c'),
+        'Ď' | 'Đ' | 'Ð' => Spelling::one('D'),
+        'ď' | 'đ' | 'ð' => Spelling::one('d'),
+        'È'..='Ë' | 'Ē' | 'Ĕ' | 'Ė' | 'Ę' | 'Ě' => Spelling::one('E'),
+        'è'..='ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => Spelling::one('e'),
+        'Ĝ' | 'Ğ' | 'Ġ' | 'Ģ' => Spelling::one('G'),
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => Spelling::one('g'),
+        'Ì'..='Ï' | 'Ī' | 'Ĭ' | 'Į' | 'İ' => Spelling::one('I'),
+        'ì'..='ï' | 'ī' | 'ĭ' | 'į' | 'ı' => Spelling::one('i'),
+        'Ł' | 'Ĺ' | 'Ľ' => Spelling::one('L'),
+        'ł' | 'ĺ' | 'ľ' => Spelling::one('l'),
+        'Ñ' | 'Ń' | 'Ň' => Spelling::one('N'),
+        'ñ' | 'ń' | 'ň' => Spelling::one('n'),
+        'Ò'..='Ö' | 'Ø' | 'Ō' | 'Ő' => Spelling::one('O'),
+        'ò'..='ö' | 'ø' | 'ō' | 'ő' => Spelling::one('o'),
+        'Œ' => Spelling::two('O', 'E'),
+        'œ' => Spelling::two('o', 'e'),
+        'Ŕ' | 'Ř' => Spelling::one('R'),
+        'ŕ' | 'ř' => Spelling::one('r'),
+        'Ś' | 'Ş' | 'Š' | 'Ș' => Spelling::one('S'),
+        'ś' | 'ş' | 'š' | 'ș' => Spelling::one('s'),
+        'ß' => Spelling::two('s', 's'),
+        'Ţ' | 'Ť' | 'Ț' => Spelling::one('T'),
+        'ţ' | 'ť' | 'ț' => Spelling::one('t'),
+        'Þ' => Spelling::two('T', 'h'),
+        'þ' => Spelling::two('t', 'h'),
+        'Ù'..='Ü' | 'Ū' | 'Ů' | 'Ű' | 'Ų' => Spelling::one('U'),
+        'ù'..='ü' | 'ū' | 'ů' | 'ű' | 'ų' => Spelling::one('u'),
+        'Ý' | 'Ÿ' => Spelling::one('Y'),
+        'ý' | 'ÿ' => Spelling::one('y'),
+        'Ź' | 'Ż' | 'Ž' => Spelling::one('Z'),
+        'ź' | 'ż' | 'ž' => Spelling::one('z'),
+
+        // ===== Cyrillic =====
+        'А' => Spelling::one('A'),
+        'В' => Spelling::one('B'),
+        'Е' | 'Ё' => Spelling::one('E'),
+        'К' => Spelling::one('K'),
+        'М' => Spelling::one('M'),
+        'Н' => Spelling::one('H'),
+        'О' => Spelling::one('O'),
+        'Р' => Spelling::one('P'),
+        'С' => Spelling::one('C'),
+        'Т' => Spelling::one('T'),
+        'Х' => Spelling::one('X'),
+        'а'..='я' => Spelling::one(shift(c, -0x20)), // ← A02 only carries uppercase
+        'ё' => Spelling::one('Ё'),
+
+        _ => None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use super::{codes, default_replacement, Codes, MAX_CODES};
+    use crate::cgrom::CgRom;
+
+    const R: u8 = 0x7F; // ← not what either ROM defaults to, so a replacement stands out
+
+    fn a00(c: char) -> (Vec<u8>, bool) {
+        let codes = codes(c, CgRom::A00, R);
+        let replaced = codes.replaced;
+        (codes.collect(), replaced)
+    }
+
+    #[test]
+    fn respells() {
+        assert_eq!(a00('ｶ'), (vec![0xB6], false));       // ← half-width kana are A00's own codes
+        assert_eq!(a00('か'), (vec![0xB6], false));       // ← hiragana → katakana
+        assert_eq!(a00('が'), (vec![0xB6, 0xDE], false)); // ← が → ガ → カ゛, the second pass
+        assert_eq!(a00('ぴ'), (vec![0xCB, 0xDF], false)); // ← ぴ → ピ → ヒ゜
+        assert_eq!(a00('Ａ'), (vec![b'A'], false));       // ← full-width ASCII
+        assert_eq!(a00('’'), (vec![b'\''], false));
+        assert_eq!(a00('—'), (vec![b'-'], false));
+        assert_eq!(a00('…'), (b"...".to_vec(), false));
+        assert_eq!(a00('€'), (b"EUR".to_vec(), false));
+        assert_eq!(a00('é'), (vec![b'e'], false));
+        assert_eq!(a00('œ'), (b"oe".to_vec(), false));
+        assert_eq!(a00('Р'), (vec![b'P'], false));        // ← Cyrillic lookalike
+    }
+
+    #[test]
+    fn replaces() {
+        assert_eq!(a00('☃'), (vec![R], true));
+        assert_eq!(a00('ж'), (vec![R], true));     // ← ж → Ж, which A00 doesn't have either
+        assert_eq!(a00('ゟ'), (vec![R], true));
+    }
+
+    #[test]
+    fn caps_at_max_codes() {
+        let mut codes = Codes::of(b'a');
+        for code in b"bcdefgh" {
+            codes.push(*code);
+        }
+
+        assert_eq!(codes.width() as usize, MAX_CODES);
+        assert_eq!(codes.collect::<Vec<u8>>(), b"abcdef");
+    }
+
+    #[test]
+    fn default_replacements() {
+        assert_eq!(default_replacement(CgRom::A00), 0xFF); // ← full block
+        assert_eq!(default_replacement(CgRom::A02), b'?'); // ← A02's 0xFF is ÿ
+        assert_eq!(codes('☃', CgRom::A02, default_replacement(CgRom::A02)).collect::<Vec<u8>>(), b"?");
+    }
+}
@@ -0,0 +1,131 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use crate::cgram::GlyphBank;
+
+// Bars and gauges drawn with partial-block glyphs. The widgets only decide which block char goes in which cell;
+// the glyphs themselves live in the GlyphBank like any other custom char, so paint() uploads them on demand and
+// a screen never needs more than two of them at once (the full block plus whichever partial cell is the tip).
+
+const HBLOCKS: [char; 5] = ['▏', '▎', '▍', '▌', '█']; // ← 1-5 columns lit, left-aligned
+const VBLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█']; // ← 1-8 rows lit, bottom-aligned
+
+const fn hblock(cols: u8) -> [u8; 8] {
+    [(0x1F << (5 - cols)) & 0x1F; 8]
+}
+
+const fn vblock(rows: u8) -> [u8; 8] {
+    let mut glyph = [0u8; 8];
+    let mut r = 8 - rows as usize;
+
+    while r < 8 {
+        glyph[r] = 0x1F;
+        r += 1;
+    }
+
+    glyph
+}
+
+pub fn register(bank: &mut GlyphBank) {
+    for (i, c) in HBLOCKS.iter().enumerate() {
+        bank.register(*c, hblock(i as u8 + 1));
+    }
+
+    for (i, c) in VBLOCKS.iter().enumerate().take(7) { // ← █ is already in from the bar
+        bank.register(*c, vblock(i as u8 + 1));
+    }
+}
+
+fn lit(value: u16, max: u16, px: u32) -> u32 { // ← pixels to light out of px, rounded; value past max just fills it
+    if max == 0 {
+        return 0;
+    }
+
+    (value.min(max) as u32 * px + max as u32 / 2) / max as u32
+}
+
+pub struct Bar { // ← horizontal, fills left → right at 5 px per cell
+    pub row: u8,
+    pub col: u8,
+    pub width: u8 // ← in cells
+}
+
+impl Bar {
+    pub fn new(row: u8, col: u8, width: u8) -> Self {
+        Self { row, col, width }
+    }
+
+    pub fn text(&self, value: u16, max: u16) -> String {
+        let px = lit(value, max, self.width as u32 * 5);
+        let mut text = String::new();
+
+        for cell in 0..self.width as u32 {
+            text.push(match px.saturating_sub(cell * 5).min(5) {
+                0 => ' ',
+                n => HBLOCKS[n as usize - 1]
+            });
+        }
+
+        text
+    }
+}
+
+pub struct Gauge { // ← vertical, fills bottom → top at 8 px per cell
+    pub row: u8,   // ← top cell
+    pub col: u8,
+    pub height: u8 // ← in cells
+}
+
+impl Gauge {
+    pub fn new(row: u8, col: u8, height: u8) -> Self {
+        Self { row, col, height }
+    }
+
+    pub fn cells(&self, value: u16, max: u16) -> Vec<char> { // ← top → bottom
+        let px = lit(value, max, self.height as u32 * 8);
+
+        (0..self.height as u32).rev().map(|cell| match px.saturating_sub(cell * 8).min(8) {
+            0 => ' ',
+            n => VBLOCKS[n as usize - 1]
+        }).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use super::{hblock, vblock, Bar, Gauge};
+
+    #[test]
+    fn bar() { // ← 4 cells = 20 px
+        let bar = Bar::new(0, 0, 4);
+        assert_eq!(bar.text(0, 100), "    ");
+        assert_eq!(bar.text(100, 100), "████");
+        assert_eq!(bar.text(7, 20), "█▎  ");   // ← tip cell partly lit
+        assert_eq!(bar.text(10, 20), "██  ");  // ← tip lands on a cell edge
+        assert_eq!(bar.text(1, 40), "▏   ");   // ← half a px rounds up
+        assert_eq!(bar.text(1, 41), "    ");   // ← just under half rounds down
+        assert_eq!(bar.text(39, 40), "████");
+        assert_eq!(bar.text(500, 100), "████"); // ← past max just fills
+        assert_eq!(bar.text(5, 0), "    ");
+    }
+
+    #[test]
+    fn gauge() { // ← 2 cells = 16 px, top → bottom
+        let gauge = Gauge::new(0, 15, 2);
+        assert_eq!(gauge.cells(0, 16), vec![' ', ' ']);
+        assert_eq!(gauge.cells(16, 16), vec!['█', '█']);
+        assert_eq!(gauge.cells(3, 16), vec![' ', '▃']);
+        assert_eq!(gauge.cells(9, 16), vec!['▁', '█']);
+        assert_eq!(gauge.cells(1, 32), vec![' ', '▁']); // ← half a px rounds up
+        assert_eq!(gauge.cells(999, 16), vec!['█', '█']); // ← clamped to max
+        assert_eq!(gauge.cells(3, 0), vec![' ', ' ']);
+    }
+
+    #[test]
+    fn blocks() {
+        assert_eq!(hblock(1), [0b10000; 8]);
+        assert_eq!(hblock(5), [0b11111; 8]);
+        assert_eq!(vblock(1), [0, 0, 0, 0, 0, 0, 0, 0x1F]);
+        assert_eq!(vblock(8), [0x1F; 8]);
+    }
+}